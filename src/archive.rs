@@ -0,0 +1,115 @@
+use crate::languages::get_syntax_from_path;
+use crate::source::{comments_from_encoded, Encoding, OwnedEvent};
+
+/// Parses the comments of each entry of an archive, such as a zip or
+/// tar file of source files, and returns the comments of each entry
+/// along with its name.
+///
+/// The `entries` are pairs of the name, i.e. the path within the
+/// archive, and the bytes of the entry, such that reading the archive
+/// is left to the caller. The [syntax rules] of each entry are chosen
+/// by [`get_syntax_from_path`] on its name. Entries in an unknown or
+/// unsupported language are skipped, as well as binary entries, i.e.
+/// those containing a `NUL` byte or invalid UTF-8.
+///
+/// The ranges of the [`OwnedEvent`]s are byte ranges in the text of
+/// the entry, excluding a leading byte order mark.
+///
+/// [syntax rules]: enum.SyntaxRule.html
+/// [`get_syntax_from_path`]: fn.get_syntax_from_path.html
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::parse_archive;
+/// let entries = vec![
+///     ("src/main.rs", b"fn main() {} // Foo\n".to_vec()),
+///     ("logo.png", b"\x89PNG\r\n\x1a\n\0".to_vec()),
+/// ];
+///
+/// let comments = parse_archive(entries);
+///
+/// assert_eq!(comments.len(), 1);
+/// assert_eq!(comments[0].0, "src/main.rs");
+/// assert_eq!(comments[0].1[0].text, " Foo");
+/// ```
+pub fn parse_archive<I, N, B>(entries: I) -> Vec<(String, Vec<OwnedEvent>)>
+where
+    I: IntoIterator<Item = (N, B)>,
+    N: AsRef<str>,
+    B: AsRef<[u8]>,
+{
+    entries
+        .into_iter()
+        .filter_map(|(name, bytes)| {
+            let (name, bytes) = (name.as_ref(), bytes.as_ref());
+
+            let rules = get_syntax_from_path(name).ok()?;
+            if is_binary(bytes) {
+                return None;
+            }
+
+            let comments = comments_from_encoded(bytes, Encoding::Utf8, rules);
+            Some((name.to_string(), comments))
+        })
+        .collect()
+}
+
+/// Returns `true` if `bytes` look like a binary file, i.e. contain
+/// a `NUL` byte or are not valid UTF-8.
+#[inline]
+fn is_binary(bytes: &[u8]) -> bool {
+    bytes.contains(&0) || std::str::from_utf8(bytes).is_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_archive, EventKind};
+
+    #[test]
+    fn in_memory_archive() {
+        let entries: Vec<(String, Vec<u8>)> = vec![
+            ("crate/".into(), Vec::new()),
+            (
+                "crate/src/lib.rs".into(),
+                b"/// Foo\npub fn foo() {}\n".to_vec(),
+            ),
+            (
+                "crate/build.py".into(),
+                b"\xEF\xBB\xBFimport os  # Bar\n".to_vec(),
+            ),
+            ("crate/data.rs".into(), b"// \xFF\xFE\n".to_vec()),
+            ("crate/README.unknown".into(), b"// Baz\n".to_vec()),
+        ];
+
+        let comments = parse_archive(entries);
+
+        let summary = comments
+            .iter()
+            .map(|(name, events)| {
+                let events = events
+                    .iter()
+                    .map(|event| (event.kind, event.text.as_str(), event.raw_range.clone()))
+                    .collect::<Vec<_>>();
+                (name.as_str(), events)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(
+            summary[0],
+            (
+                "crate/src/lib.rs",
+                vec![(EventKind::LineComment, " Foo", 0..7)]
+            )
+        );
+        assert_eq!(
+            summary[1],
+            (
+                "crate/build.py",
+                vec![(EventKind::LineComment, " Bar", 0..16)]
+            )
+        );
+    }
+}
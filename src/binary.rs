@@ -0,0 +1,178 @@
+use std::ops::Range;
+
+use crate::parse::{range_in, CommentParser, Event, EventKind};
+use crate::syntax::SyntaxRule;
+
+/// Parses `source` based on `rules`, and returns the comments encoded
+/// compactly as bytes, which can be decoded with [`decode_comments`].
+///
+/// Only the positions of the comments are encoded, such that the text
+/// is stored once, i.e. in `source`. Each comment is a kind byte,
+/// followed by the start and length of its [`raw`] as varints, as well
+/// as the offset and length of its [`text`], and for line comments of
+/// its delimiter, relative to the start of [`raw`].
+///
+/// [`decode_comments`]: fn.decode_comments.html
+/// [`raw`]: enum.Event.html#method.raw
+/// [`text`]: enum.Event.html#method.text
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{decode_comments, encode_comments, get_syntax, CommentParser};
+/// let code = "// Foo\nfn foo() {} /* Bar */\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let bytes = encode_comments(code, rules);
+/// assert_eq!(bytes.len(), 12);
+///
+/// let comments = decode_comments(&bytes, code);
+/// assert_eq!(comments, CommentParser::new(code, rules).collect::<Vec<_>>());
+/// ```
+pub fn encode_comments(source: &str, rules: &[SyntaxRule]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for event in CommentParser::new(source, rules) {
+        let raw = range_in(source, event.raw());
+        let text = range_in(event.raw(), event.text());
+
+        bytes.push(match event.kind() {
+            EventKind::LineComment => 0,
+            EventKind::BlockComment => 1,
+            EventKind::Shebang => 2,
+            EventKind::String => 3,
+        });
+        encode_range(&mut bytes, raw);
+        encode_range(&mut bytes, text);
+
+        if let Event::LineComment(raw, _, delim) = event {
+            encode_range(&mut bytes, range_in(raw, delim));
+        }
+    }
+
+    bytes
+}
+
+/// Decodes the comments encoded by [`encode_comments`], where the
+/// [`Event`]s are sliced from `source`.
+///
+/// [`encode_comments`]: fn.encode_comments.html
+/// [`Event`]: enum.Event.html
+///
+/// # Panics
+///
+/// Panics if `bytes` were not encoded from `source`, e.g. if a range
+/// is out of bounds or `bytes` is truncated.
+pub fn decode_comments<'a>(bytes: &[u8], source: &'a str) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let kind = bytes[i];
+        i += 1;
+
+        let raw = &source[decode_range(bytes, &mut i)];
+        let text = &raw[decode_range(bytes, &mut i)];
+
+        events.push(match kind {
+            0 => Event::LineComment(raw, text, &raw[decode_range(bytes, &mut i)]),
+            1 => Event::BlockComment(raw, text),
+            2 => Event::Shebang(raw, text),
+            3 => Event::String(raw, text),
+            _ => panic!("invalid comment kind {}", kind),
+        });
+    }
+
+    events
+}
+
+/// Appends `range` to `bytes` as the start and length varints.
+#[inline]
+fn encode_range(bytes: &mut Vec<u8>, range: Range<usize>) {
+    encode_varint(bytes, range.start);
+    encode_varint(bytes, range.end - range.start);
+}
+
+/// Returns the range encoded at `bytes[*i..]`, and advances `i` past it.
+#[inline]
+fn decode_range(bytes: &[u8], i: &mut usize) -> Range<usize> {
+    let start = decode_varint(bytes, i);
+    let len = decode_varint(bytes, i);
+    start..start + len
+}
+
+/// Appends `n` to `bytes` as an unsigned LEB128 varint, i.e. 7 bits
+/// per byte, where the high bit is set on all but the last byte.
+fn encode_varint(bytes: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        bytes.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+/// Returns the varint encoded at `bytes[*i..]`, and advances `i` past it.
+fn decode_varint(bytes: &[u8], i: &mut usize) -> usize {
+    let mut n = 0;
+    let mut shift = 0;
+
+    loop {
+        let byte = *bytes.get(*i).expect("truncated varint");
+        *i += 1;
+
+        n |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return n;
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_varint, encode_varint};
+    use crate::{decode_comments, encode_comments, get_syntax, CommentParser};
+
+    #[test]
+    fn round_trip() {
+        let code = format!(
+            "#!/bin/sh\n# Foo\necho \"# Bar\" # Baz\n{}\n  #Qux\n",
+            "x".repeat(200)
+        );
+        let rules = get_syntax("shell").unwrap();
+
+        let bytes = encode_comments(&code, rules);
+        let comments = decode_comments(&bytes, &code);
+
+        assert_eq!(comments.len(), 4);
+        assert_eq!(
+            comments,
+            CommentParser::new(&code, rules).collect::<Vec<_>>()
+        );
+        assert_eq!(comments[3].text(), "Qux");
+        assert_eq!(comments[3].open_delim(), "#");
+
+        let code = "/** Foo */\n//! Bar\nlet s = \"// Baz\"; /* /* Qux */ */\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let bytes = encode_comments(code, rules);
+        assert_eq!(
+            decode_comments(&bytes, code),
+            CommentParser::new(code, rules).collect::<Vec<_>>()
+        );
+
+        assert!(encode_comments("fn main() {}", rules).is_empty());
+    }
+
+    #[test]
+    fn varint() {
+        for &n in &[0, 1, 127, 128, 300, 16_384, usize::MAX] {
+            let mut bytes = Vec::new();
+            encode_varint(&mut bytes, n);
+
+            let mut i = 0;
+            assert_eq!(decode_varint(&bytes, &mut i), n);
+            assert_eq!(i, bytes.len());
+        }
+    }
+}
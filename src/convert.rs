@@ -0,0 +1,383 @@
+use line_span::find_line_range;
+
+use crate::parse::{indentation, range_in, CommentParser, EventKind};
+use crate::syntax::SyntaxRule;
+
+/// Returns `source` with every comment of the given `kind` rewritten
+/// to use the delimiters `to_open` and `to_close`, while preserving
+/// the comment text and all code.
+///
+/// If `to_close` is empty, then comments are converted into line
+/// comments. A multi-line block comment thereby becomes multiple
+/// line comments, where leading and trailing blank lines are dropped,
+/// and each line is indented like the line the comment starts on.
+/// Otherwise, each comment becomes a single comment, e.g. each line
+/// comment becomes a single-line block comment.
+///
+/// Comments are left as is, if converting them would change the code:
+///
+/// - The comment is followed by code on the same line, and
+///   `to_close` is empty.
+/// - The comment text contains `to_close`.
+/// - The comment is an unterminated block comment.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{convert_comments, get_syntax, EventKind};
+/// let code = "/* Foo\n Bar */\nfn foo() {} // Baz\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let code = convert_comments(code, rules, EventKind::BlockComment, "//", "");
+/// assert_eq!(code, "// Foo\n// Bar\nfn foo() {} // Baz\n");
+///
+/// let code = convert_comments(&code, rules, EventKind::LineComment, "/*", "*/");
+/// assert_eq!(code, "/* Foo */\n/* Bar */\nfn foo() {} /* Baz */\n");
+/// ```
+pub fn convert_comments(
+    source: &str,
+    rules: &[SyntaxRule],
+    kind: EventKind,
+    to_open: &str,
+    to_close: &str,
+) -> String {
+    let mut converted = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for event in CommentParser::new(source, rules) {
+        if event.kind() != kind {
+            continue;
+        }
+
+        if kind == EventKind::BlockComment && event.close_delim().is_empty() {
+            continue;
+        }
+
+        let start = range_in(source, event.open_delim()).start;
+        let end = range_in(source, event.text()).end + event.close_delim().len();
+        let text = event.text();
+
+        let line = find_line_range(source, start);
+
+        if to_close.is_empty() {
+            let line_end = find_line_range(source, end).end;
+            if !source[end..line_end].trim().is_empty() {
+                continue;
+            }
+
+            let indent = indentation(&source[line.start..start]);
+
+            let mut lines = text.lines().collect::<Vec<_>>();
+            if lines.is_empty() {
+                lines.push("");
+            }
+            if lines.len() > 1 && lines[lines.len() - 1].trim().is_empty() {
+                lines.pop();
+            }
+            let first = if lines.len() > 1 && lines[0].trim().is_empty() {
+                lines.remove(0);
+                None
+            } else {
+                Some(lines.remove(0))
+            };
+
+            converted.push_str(&source[last..start]);
+
+            if let Some(first) = first {
+                converted.push_str(to_open);
+                converted.push_str(first.trim_end());
+            }
+
+            for (i, line) in lines.into_iter().enumerate() {
+                if i > 0 || first.is_some() {
+                    converted.push('\n');
+                    converted.push_str(indent);
+                }
+
+                converted.push_str(to_open);
+                converted.push_str(line.strip_prefix(indent).unwrap_or(line).trim_end());
+            }
+        } else {
+            if text.contains(to_close) {
+                continue;
+            }
+
+            converted.push_str(&source[last..start]);
+            converted.push_str(to_open);
+            converted.push_str(text);
+
+            let ends_with_whitespace = text.ends_with(char::is_whitespace);
+            if text.starts_with(char::is_whitespace) && !ends_with_whitespace {
+                converted.push(' ');
+            }
+
+            converted.push_str(to_close);
+        }
+
+        last = end;
+    }
+
+    converted.push_str(&source[last..]);
+    converted
+}
+
+/// Options for [`strip_comments`].
+///
+/// [`strip_comments`]: fn.strip_comments.html
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct StripOptions {
+    /// If `Some(c)`, then each removed byte is replaced with `c`,
+    /// except for newlines, which are preserved. If `None`, then the
+    /// comments are removed entirely.
+    ///
+    /// The `c` must be ASCII, i.e. a single byte, such that the byte
+    /// offsets of the code are preserved.
+    ///
+    /// The default is `None`.
+    pub fill: Option<char>,
+}
+
+/// Returns `source` with all comments removed, or replaced
+/// as specified by `options`, while preserving all code.
+///
+/// For line comments, only the comment itself is removed, i.e. from
+/// the delimiter to the end of the line, and not any code before it.
+///
+/// # Panics
+///
+/// Panics if the [`fill`] of `options` is not ASCII.
+///
+/// [`fill`]: struct.StripOptions.html#structfield.fill
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, strip_comments, StripOptions};
+/// let code = "foo(); // Foo\nbar(/* Bar */);\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let stripped = strip_comments(code, rules, StripOptions::default());
+/// assert_eq!(stripped, "foo(); \nbar();\n");
+///
+/// let stripped = strip_comments(code, rules, StripOptions { fill: Some('.') });
+/// assert_eq!(stripped, "foo(); ......\nbar(.........);\n");
+/// ```
+pub fn strip_comments(source: &str, rules: &[SyntaxRule], options: StripOptions) -> String {
+    strip_comments_of_kind(
+        source,
+        rules,
+        &[EventKind::LineComment, EventKind::BlockComment],
+        options,
+    )
+}
+
+/// Returns `source` with only the comments of the given `kinds`
+/// removed, or replaced as specified by `options`, like
+/// [`strip_comments`]. All other comments are preserved.
+///
+/// This is useful for e.g. removing block comments, while keeping
+/// line comments.
+///
+/// [`strip_comments`]: fn.strip_comments.html
+///
+/// # Panics
+///
+/// Panics if the [`fill`] of `options` is not ASCII.
+///
+/// [`fill`]: struct.StripOptions.html#structfield.fill
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, strip_comments_of_kind, EventKind, StripOptions};
+/// let code = "foo(); // Foo\nbar(/* Bar */);\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let kinds = [EventKind::BlockComment];
+/// let stripped = strip_comments_of_kind(code, rules, &kinds, StripOptions::default());
+/// assert_eq!(stripped, "foo(); // Foo\nbar();\n");
+/// ```
+pub fn strip_comments_of_kind(
+    source: &str,
+    rules: &[SyntaxRule],
+    kinds: &[EventKind],
+    options: StripOptions,
+) -> String {
+    assert!(
+        options.fill.map_or(true, |fill| fill.is_ascii()),
+        "fill is not ASCII"
+    );
+
+    let mut stripped = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for event in CommentParser::new(source, rules) {
+        if !kinds.contains(&event.kind()) {
+            continue;
+        }
+
+        let start = range_in(source, event.open_delim()).start;
+        let end = range_in(source, event.text()).end + event.close_delim().len();
+
+        stripped.push_str(&source[last..start]);
+
+        if let Some(fill) = options.fill {
+            for &b in &source.as_bytes()[start..end] {
+                match b {
+                    b'\n' | b'\r' => stripped.push(b as char),
+                    _ => stripped.push(fill),
+                }
+            }
+        }
+
+        last = end;
+    }
+
+    stripped.push_str(&source[last..]);
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        convert_comments, get_syntax, strip_comments, strip_comments_of_kind, EventKind,
+        StripOptions,
+    };
+
+    #[test]
+    fn block_to_line() {
+        let code = "
+fn main() {
+    /* Foo
+       Bar */
+    let x = 1; /* Baz */
+    let y = 2; /* Qux */ let z = 3;
+    /*
+     * Banner
+     */
+    /**/
+}
+";
+        let rules = get_syntax("rust").unwrap();
+
+        let converted = convert_comments(code, rules, EventKind::BlockComment, "//", "");
+        assert_eq!(
+            converted,
+            "
+fn main() {
+    // Foo
+    //   Bar
+    let x = 1; // Baz
+    let y = 2; /* Qux */ let z = 3;
+    // * Banner
+    //
+}
+"
+        );
+    }
+
+    #[test]
+    fn line_to_block() {
+        let code = "
+// Foo
+let x = 1; // Bar
+//Baz
+// Contains */
+let s = \"// Not a comment\";
+";
+        let rules = get_syntax("rust").unwrap();
+
+        let converted = convert_comments(code, rules, EventKind::LineComment, "/*", "*/");
+        assert_eq!(
+            converted,
+            "
+/* Foo */
+let x = 1; /* Bar */
+/*Baz*/
+// Contains */
+let s = \"// Not a comment\";
+"
+        );
+    }
+
+    #[test]
+    fn line_to_line() {
+        let code = "# Foo\nx = 1  # Bar \n";
+        let rules = get_syntax("python").unwrap();
+
+        let converted = convert_comments(code, rules, EventKind::LineComment, "//", "");
+        assert_eq!(converted, "// Foo\nx = 1  // Bar\n");
+    }
+
+    #[test]
+    fn strip_remove() {
+        let code = "let x = 1; // Foo\n/* Bar\n   Baz */let y = \"// Qux\";\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let stripped = strip_comments(code, rules, StripOptions { fill: None });
+        assert_eq!(stripped, "let x = 1; \nlet y = \"// Qux\";\n");
+    }
+
+    #[test]
+    fn strip_fill() {
+        let code = "let x = 1; // Foo\r\n/* Bar\n   Baz */let y = \"// Qux\";\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let stripped = strip_comments(code, rules, StripOptions { fill: Some(' ') });
+        assert_eq!(stripped.len(), code.len());
+        assert_eq!(
+            stripped,
+            "let x = 1;       \r\n      \n         let y = \"// Qux\";\n"
+        );
+
+        let stripped = strip_comments(code, rules, StripOptions { fill: Some('.') });
+        assert_eq!(stripped.lines().count(), code.lines().count());
+        assert_eq!(
+            stripped,
+            "let x = 1; ......\r\n......\n.........let y = \"// Qux\";\n"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn strip_fill_not_ascii() {
+        let rules = get_syntax("rust").unwrap();
+
+        strip_comments(
+            "// Foo",
+            rules,
+            StripOptions {
+                fill: Some('\u{B7}'),
+            },
+        );
+    }
+
+    #[test]
+    fn strip_only_block() {
+        let code = "let x = 1; // Foo\n/* Bar */let y = \"/* Baz */\"; /* Qux */\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let kinds = [EventKind::BlockComment];
+        let stripped = strip_comments_of_kind(code, rules, &kinds, StripOptions::default());
+        assert_eq!(stripped, "let x = 1; // Foo\nlet y = \"/* Baz */\"; \n");
+    }
+
+    #[test]
+    fn strip_only_line() {
+        let code = "let x = 1; // Foo\n/* Bar */let y = \"// Baz\"; // Qux\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let kinds = [EventKind::LineComment];
+        let stripped = strip_comments_of_kind(code, rules, &kinds, StripOptions::default());
+        assert_eq!(stripped, "let x = 1; \n/* Bar */let y = \"// Baz\"; \n");
+    }
+
+    #[test]
+    fn unterminated_block() {
+        let code = "int x; /* Foo\n";
+        let rules = get_syntax("c").unwrap();
+
+        let converted = convert_comments(code, rules, EventKind::BlockComment, "//", "");
+        assert_eq!(converted, code);
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::Range;
+
+use crate::parse::EventKind;
+
+/// `CustomMatcher` matches comments and strings with custom logic,
+/// for languages whose rules cannot be expressed by [`SyntaxRule`]s,
+/// e.g. context-sensitive ones. Use [`CommentParser::with_matcher`]
+/// to plug it into a parser.
+///
+/// [`SyntaxRule`]: enum.SyntaxRule.html
+/// [`CommentParser::with_matcher`]: struct.CommentParser.html#method.with_matcher
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{CommentParser, CustomMatcher, EventKind, MatchResult};
+/// // Matches `#` line comments, only when preceded by whitespace
+/// struct Hash;
+///
+/// impl CustomMatcher for Hash {
+///     fn match_at(&self, bytes: &[u8], pos: usize) -> Option<MatchResult> {
+///         if bytes[pos] != b'#' || (pos > 0 && !bytes[pos - 1].is_ascii_whitespace()) {
+///             return None;
+///         }
+///
+///         let end = bytes[pos..]
+///             .iter()
+///             .position(|&b| b == b'\n')
+///             .map_or(bytes.len(), |i| pos + i);
+///
+///         Some(MatchResult {
+///             kind: EventKind::LineComment,
+///             text: pos + 1..end,
+///             end,
+///         })
+///     }
+/// }
+///
+/// let code = "echo $# # Foo";
+///
+/// let mut parser = CommentParser::new(code, &[]).with_matcher(Hash);
+///
+/// assert_eq!(parser.next().unwrap().text(), " Foo");
+/// assert_eq!(parser.next(), None);
+/// ```
+pub trait CustomMatcher {
+    /// Returns the match starting at byte index `pos` of `bytes`,
+    /// or `None` if nothing matches at `pos`.
+    fn match_at(&self, bytes: &[u8], pos: usize) -> Option<MatchResult>;
+}
+
+/// `MatchResult` is a comment or string matched by a [`CustomMatcher`].
+///
+/// The opening delimiter is the bytes from the matched position to
+/// the start of `text`, and the closing delimiter is the bytes from
+/// the end of `text` to `end`.
+///
+/// [`CustomMatcher`]: trait.CustomMatcher.html
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MatchResult {
+    /// The kind of the matched [`Event`].
+    ///
+    /// [`Event`]: enum.Event.html
+    pub kind: EventKind,
+    /// The byte range of the [`text`] of the match.
+    ///
+    /// [`text`]: enum.Event.html#method.text
+    pub text: Range<usize>,
+    /// The byte index after the match, i.e. where parsing continues.
+    pub end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CommentParser, CustomMatcher, Event, EventKind, MatchResult, SyntaxRule};
+
+    struct Hash;
+
+    impl CustomMatcher for Hash {
+        fn match_at(&self, bytes: &[u8], pos: usize) -> Option<MatchResult> {
+            if bytes[pos] != b'#' || (pos > 0 && !bytes[pos - 1].is_ascii_whitespace()) {
+                return None;
+            }
+
+            let end = bytes[pos..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |i| pos + i);
+
+            Some(MatchResult {
+                kind: EventKind::LineComment,
+                text: pos + 1..end,
+                end,
+            })
+        }
+    }
+
+    #[test]
+    fn whitespace_hash() {
+        let code = "# Foo\necho ${x#y} $# \"a # b\" # Bar\n";
+        let rules = [SyntaxRule::String(b"\"")];
+
+        let events = CommentParser::new(code, &rules)
+            .with_matcher(Hash)
+            .include_strings(true)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            [
+                Event::LineComment("# Foo", " Foo", "#"),
+                Event::String("\"a # b\"", "a # b"),
+                Event::LineComment("echo ${x#y} $# \"a # b\" # Bar", " Bar", "#"),
+            ]
+        );
+    }
+}
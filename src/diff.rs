@@ -0,0 +1,170 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::parse::{CommentParser, Event};
+use crate::source::OwnedEvent;
+use crate::syntax::SyntaxRule;
+
+/// `CommentDiff` is a change of a comment between two versions of
+/// a source, returned by [`diff_comments`].
+///
+/// [`diff_comments`]: fn.diff_comments.html
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum CommentDiff {
+    /// `Added(new)`, the comment only exists in the new source.
+    Added(OwnedEvent),
+    /// `Removed(old)`, the comment only exists in the old source.
+    Removed(OwnedEvent),
+    /// `Unchanged(old, new)`, the comment exists in both sources,
+    /// possibly at different positions.
+    Unchanged(OwnedEvent, OwnedEvent),
+}
+
+/// Parses `old` and `new` based on `rules`, and returns the comments
+/// which were added, removed or left unchanged between them.
+///
+/// Comments are matched by their [`open_delim`] and [`text`], ignoring
+/// the whitespace around each line, such that a comment which only
+/// moved, or only changed its line endings, surrounding whitespace or
+/// the code before it, is unchanged. Identical comments are matched in
+/// order of appearance.
+///
+/// The added and unchanged comments are returned in the order of `new`,
+/// followed by the removed comments in the order of `old`.
+///
+/// [`open_delim`]: enum.Event.html#method.open_delim
+/// [`text`]: enum.Event.html#method.text
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{diff_comments, get_syntax, CommentDiff};
+/// let old = "// Foo\nfn foo() {}\n";
+/// let new = "fn foo() {}\n// Foo\n// Bar\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let diff = diff_comments(old, new, rules);
+///
+/// assert!(matches!(&diff[0], CommentDiff::Unchanged(old, new) if old.text == new.text));
+/// assert!(matches!(&diff[1], CommentDiff::Added(new) if new.text == " Bar"));
+/// ```
+pub fn diff_comments(old: &str, new: &str, rules: &[SyntaxRule]) -> Vec<CommentDiff> {
+    let mut old_comments = Vec::new();
+    let mut keys: HashMap<String, VecDeque<usize>> = HashMap::new();
+
+    for event in CommentParser::new(old, rules) {
+        keys.entry(key(&event))
+            .or_default()
+            .push_back(old_comments.len());
+        old_comments.push(Some(OwnedEvent::from_event(&event, old)));
+    }
+
+    let mut diff = Vec::new();
+
+    for event in CommentParser::new(new, rules) {
+        let matched = keys.get_mut(&key(&event)).and_then(VecDeque::pop_front);
+        let new_comment = OwnedEvent::from_event(&event, new);
+
+        diff.push(match matched {
+            Some(i) => {
+                let old_comment = old_comments[i].take().unwrap();
+                CommentDiff::Unchanged(old_comment, new_comment)
+            }
+            None => CommentDiff::Added(new_comment),
+        });
+    }
+
+    diff.extend(old_comments.into_iter().flatten().map(CommentDiff::Removed));
+
+    diff
+}
+
+/// Returns the key used to match `event` across sources, i.e. its
+/// [`open_delim`] followed by its [`text`], with each line trimmed.
+///
+/// [`open_delim`]: enum.Event.html#method.open_delim
+/// [`text`]: enum.Event.html#method.text
+fn key(event: &Event) -> String {
+    let text = event
+        .text()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{}{}", event.open_delim(), text.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{diff_comments, get_syntax, CommentDiff};
+
+    fn summary(diff: &[CommentDiff]) -> Vec<(&str, &str)> {
+        diff.iter()
+            .map(|diff| match diff {
+                CommentDiff::Added(new) => ("added", new.text.as_str()),
+                CommentDiff::Removed(old) => ("removed", old.text.as_str()),
+                CommentDiff::Unchanged(_, new) => ("unchanged", new.text.as_str()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn added() {
+        let old = "// Foo\nfn foo() {}\n";
+        let new = "// Foo\nfn foo() {} // Bar\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let diff = diff_comments(old, new, rules);
+        assert_eq!(summary(&diff), [("unchanged", " Foo"), ("added", " Bar")]);
+    }
+
+    #[test]
+    fn removed() {
+        let old = "// Foo\n/* Bar */\nfn foo() {}\n";
+        let new = "// Foo\nfn foo() {}\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let diff = diff_comments(old, new, rules);
+        assert_eq!(
+            summary(&diff),
+            [("unchanged", " Foo"), ("removed", " Bar ")]
+        );
+    }
+
+    #[test]
+    fn code_changed() {
+        let old = "let x = 1; // Foo\n/* Bar */ let y = 1;\n";
+        let new = "let x = 2; // Foo\nlet y = 2; /* Bar */\n// Bar\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let diff = diff_comments(old, new, rules);
+        assert_eq!(
+            summary(&diff),
+            [
+                ("unchanged", " Foo"),
+                ("unchanged", " Bar "),
+                ("added", " Bar")
+            ]
+        );
+    }
+
+    #[test]
+    fn moved() {
+        let old = "// Foo\nfn foo() {}\n// Bar\n";
+        let new = "// Bar\r\nfn foo() {}\r\n\r\n    // Foo  \r\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let diff = diff_comments(old, new, rules);
+        assert_eq!(
+            summary(&diff),
+            [("unchanged", " Bar"), ("unchanged", " Foo  ")]
+        );
+
+        match &diff[1] {
+            CommentDiff::Unchanged(old, new) => {
+                assert_eq!(old.raw_range, 0..6);
+                assert_eq!(new.raw_range, 23..35);
+            }
+            diff => panic!("expected unchanged, found {:?}", diff),
+        }
+    }
+}
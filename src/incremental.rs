@@ -0,0 +1,218 @@
+use line_span::find_line_start;
+
+use crate::parse::{range_in, CommentParser, Event, EventKind};
+use crate::source::OwnedEvent;
+use crate::syntax::SyntaxRule;
+
+/// `IncrementalCommentParser` parses text which is fed in chunks,
+/// such as a file that is being appended to, and produces only the
+/// comments that are complete.
+///
+/// A block comment or string, which is unterminated at the end of the
+/// text fed so far, is buffered until more text is fed. Likewise for a
+/// line comment, which is not yet followed by a newline.
+///
+/// The ranges of the produced [`OwnedEvent`]s are byte ranges in all
+/// of the text fed so far. Text before the line of the last complete
+/// comment is discarded, such that the buffer does not grow unbounded.
+///
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, IncrementalCommentParser};
+/// let rules = get_syntax("c").unwrap();
+/// let mut parser = IncrementalCommentParser::new(rules);
+///
+/// parser.feed("int x; /* Foo");
+/// assert!(parser.take_events().is_empty());
+///
+/// parser.feed(" Bar */\n");
+/// let events = parser.take_events();
+/// assert_eq!(events[0].text, " Foo Bar ");
+/// assert_eq!(events[0].raw_range, 7..20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalCommentParser<'a> {
+    rules: &'a [SyntaxRule<'a>],
+    buffer: String,
+    index: usize,
+    base: usize,
+}
+
+impl<'a> IncrementalCommentParser<'a> {
+    /// Creates an `IncrementalCommentParser` which parses the fed
+    /// text based on `rules`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when taking events, if any [`SyntaxRule`] contains an
+    /// empty `&[u8]`.
+    ///
+    /// [`SyntaxRule`]: enum.SyntaxRule.html
+    #[inline]
+    pub fn new(rules: &'a [SyntaxRule<'a>]) -> Self {
+        Self {
+            rules,
+            buffer: String::new(),
+            index: 0,
+            base: 0,
+        }
+    }
+
+    /// Appends `more` to the text.
+    #[inline]
+    pub fn feed(&mut self, more: &str) {
+        self.buffer.push_str(more);
+    }
+
+    /// Returns the complete comments in the text fed since the last
+    /// call, as well as any previously buffered comments, which have
+    /// since been completed.
+    pub fn take_events(&mut self) -> Vec<OwnedEvent> {
+        let mut events = Vec::new();
+        let mut incomplete = None;
+
+        {
+            let buffer = &self.buffer;
+            let mut parser =
+                CommentParser::new_from(buffer, self.rules, self.index).include_strings(true);
+
+            while let Some((start, event, unterminated)) = parser.next_checked() {
+                let end = range_in(buffer, event.raw()).end;
+
+                let complete = match event.kind() {
+                    EventKind::LineComment | EventKind::Shebang => end < buffer.len(),
+                    EventKind::BlockComment | EventKind::String => !unterminated,
+                };
+                if !complete {
+                    incomplete = Some(start);
+                    break;
+                }
+
+                if !event.is_string() {
+                    events.push(self.to_owned(&event, buffer));
+                }
+
+                // Comments inside of strings end before the string
+                self.index = self.index.max(end);
+            }
+
+            // A delimiter may be split across chunks, so the last line
+            // is parsed again, once more text is fed
+            self.index = match incomplete {
+                Some(start) => start,
+                None => find_line_start(buffer, buffer.len()).max(self.index),
+            };
+        }
+
+        let line_start = find_line_start(&self.buffer, self.index);
+        self.buffer.drain(..line_start);
+        self.base += line_start;
+        self.index -= line_start;
+
+        events
+    }
+
+    /// Consumes the `IncrementalCommentParser` and returns the remaining
+    /// comments, once all text has been fed. This includes a line comment
+    /// not followed by a newline, as well as an unterminated block comment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{get_syntax, IncrementalCommentParser};
+    /// let rules = get_syntax("c").unwrap();
+    /// let mut parser = IncrementalCommentParser::new(rules);
+    ///
+    /// parser.feed("int x; // Foo");
+    /// assert!(parser.take_events().is_empty());
+    ///
+    /// let events = parser.finish();
+    /// assert_eq!(events[0].text, " Foo");
+    /// ```
+    pub fn finish(mut self) -> Vec<OwnedEvent> {
+        let mut events = self.take_events();
+
+        let buffer = &self.buffer;
+        let parser = CommentParser::new_from(buffer, self.rules, self.index);
+        events.extend(parser.map(|event| self.to_owned(&event, buffer)));
+
+        events
+    }
+
+    /// Returns `event` as an `OwnedEvent`, where the ranges are byte
+    /// ranges in all of the text fed so far.
+    fn to_owned(&self, event: &Event, buffer: &str) -> OwnedEvent {
+        let mut event = OwnedEvent::from_event(event, buffer);
+        event.raw_range = self.base + event.raw_range.start..self.base + event.raw_range.end;
+        event.text_range = self.base + event.text_range.start..self.base + event.text_range.end;
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_syntax, IncrementalCommentParser};
+
+    #[test]
+    fn block_in_halves() {
+        let rules = get_syntax("rust").unwrap();
+        let mut parser = IncrementalCommentParser::new(rules);
+
+        parser.feed("// Foo\nlet s = \"/* Bar");
+        let events = parser.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].text, " Foo");
+
+        parser.feed("\"; /* Baz\n");
+        assert!(parser.take_events().is_empty());
+
+        parser.feed("Qux */ // Quux");
+        let events = parser.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].raw, "/* Baz\nQux */");
+        assert_eq!(events[0].raw_range, 25..38);
+
+        parser.feed("\n");
+        let events = parser.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].text, " Quux");
+        assert_eq!(events[0].text_range, 41..46);
+    }
+
+    #[test]
+    fn split_delimiter() {
+        let rules = get_syntax("c").unwrap();
+        let mut parser = IncrementalCommentParser::new(rules);
+
+        parser.feed("int x; /");
+        assert!(parser.take_events().is_empty());
+
+        parser.feed("* Foo */\n");
+        let events = parser.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].text, " Foo ");
+        assert_eq!(events[0].raw_range, 7..16);
+    }
+
+    #[test]
+    fn finish() {
+        let rules = get_syntax("c").unwrap();
+        let mut parser = IncrementalCommentParser::new(rules);
+
+        parser.feed(
+            "// Foo
+int x; /* Bar",
+        );
+        assert_eq!(parser.take_events().len(), 1);
+
+        parser.feed(" */ /* Baz");
+        let events = parser.finish();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].text, " Bar ");
+        assert_eq!(events[1].text, " Baz");
+        assert_eq!(events[1].raw_range, 24..30);
+    }
+}
@@ -4,51 +4,365 @@ use crate::syntax::SyntaxRule;
 
 use SyntaxRule::*;
 
+const ASN1: [SyntaxRule; 3] = [
+    LineCommentUntil(b"--", b"--"),
+    BlockComment(b"/*", b"*/"),
+    String(b"\""),
+];
+
+// Braces in `@comment{...}` blocks are not supported, e.g. `@comment{ {} }`
+#[rustfmt::skip]
+const BIBTEX: [SyntaxRule; 4] = [
+    LineComment(b"%"),
+    BlockComment(b"@comment{", b"}"),
+    String(b"\""),
+    // Entries and field values, e.g. `@book{key, title = {100% Foo}}`
+    NestedString(b"{", b"}"),
+];
+
 const C: [SyntaxRule; 3] = [
     LineComment(b"//"),
     BlockComment(b"/*", b"*/"),
     String(b"\""),
 ];
 
-const PYTHON: [SyntaxRule; 4] = [
+const CAPNP: [SyntaxRule; 2] = [LineComment(b"#"), String(b"\"")];
+
+const COBOL: [SyntaxRule; 5] = [
+    AtColumn(6, &LineComment(b"*")),
+    AtColumn(6, &LineComment(b"/")),
+    LineComment(b"*>"),
+    String(b"\""),
+    String(b"'"),
+];
+
+const CYTHON: [SyntaxRule; 5] = [
     LineComment(b"#"),
     String(b"\"\"\""),
+    String(b"'''"),
     String(b"\""),
     String(b"'"),
 ];
 
-const RUST: [SyntaxRule; 5] = [
+const DJANGO: [SyntaxRule; 3] = [
+    TagBlockComment(b"comment", b"endcomment"),
+    BlockComment(b"{#", b"#}"),
+    BlockComment(b"<!--", b"-->"),
+];
+
+const FREEMARKER: [SyntaxRule; 2] = [BlockComment(b"<#--", b"-->"), BlockComment(b"<!--", b"-->")];
+
+#[rustfmt::skip]
+const GHERKIN: [SyntaxRule; 3] = [
+    // Comments are only allowed on their own line
+    WholeLine(&LineComment(b"#")),
+    // Doc strings
+    String(b"\"\"\""),
+    String(b"```"),
+];
+
+const HASKELL: [SyntaxRule; 3] = [
+    LineComment(b"--"),
+    NestedBlockComment(b"{-", b"-}"),
+    String(b"\""),
+];
+
+#[rustfmt::skip]
+const INI: [SyntaxRule; 2] = [
+    // `\;` is a literal `;`, e.g. `key = a\;b`
+    Unescaped(&LineComment(b";")),
+    WholeLine(&LineComment(b"#")),
+];
+
+const JAI: [SyntaxRule; 3] = [
+    LineComment(b"//"),
+    NestedBlockComment(b"/*", b"*/"),
+    String(b"\""),
+];
+
+const JAVASCRIPT: [SyntaxRule; 6] = [
+    LineComment(b"//"),
+    BlockComment(b"/*", b"*/"),
+    String(b"\""),
+    String(b"'"),
+    String(b"`"),
+    RegexLiteral,
+];
+
+const JSON5: [SyntaxRule; 4] = [
+    LineComment(b"//"),
+    BlockComment(b"/*", b"*/"),
+    String(b"\""),
+    String(b"'"),
+];
+
+// Used by both Less and Stylus. Note that an unquoted URL,
+// such as `url(//cdn.com/foo.png)`, is parsed as a line comment.
+const LESS: [SyntaxRule; 4] = [
+    LineComment(b"//"),
+    BlockComment(b"/*", b"*/"),
+    String(b"\""),
+    String(b"'"),
+];
+
+// String escapes are `\xx` hex codes, e.g. `\22` for `"`, which
+// the default backslash escape approximates
+const LLVM_IR: [SyntaxRule; 2] = [LineComment(b";"), String(b"\"")];
+
+#[rustfmt::skip]
+const MARKDOWN: [SyntaxRule; 6] = [
+    // Code spans and fenced blocks are parsed as strings, such that
+    // `<!--` within them is not a comment. Note that `\` is treated
+    // as an escape within them, unlike in Markdown
+    Unescaped(&String(b"```")),
+    Unescaped(&String(b"~~~")),
+    Unescaped(&String(b"``")),
+    Unescaped(&String(b"`")),
+    BlockComment(b"<!--", b"-->"),
+    // Link reference definitions used as comments, e.g. `[//]: # (Foo)`
+    WholeLine(&LineComment(b"[//]: #")),
+];
+
+#[rustfmt::skip]
+const MATLAB: [SyntaxRule; 3] = [
+    // `%{` and `%}` must be on their own lines
+    WholeLine(&BlockComment(b"%{", b"%}")),
+    LineComment(b"%"),
+    // `'` is not a string rule, as it is also the transpose operator
+    String(b"\""),
+];
+
+const MERMAID: [SyntaxRule; 1] = [LineComment(b"%%")];
+
+const ODIN: [SyntaxRule; 3] = [
+    LineComment(b"//"),
+    NestedBlockComment(b"/*", b"*/"),
+    String(b"\""),
+];
+
+const PLANTUML: [SyntaxRule; 2] = [LineComment(b"'"), BlockComment(b"/'", b"'/")];
+
+const POSTSCRIPT: [SyntaxRule; 2] = [LineComment(b"%"), NestedString(b"(", b")")];
+
+const PYTHON: [SyntaxRule; 5] = [
+    Shebang,
+    LineComment(b"#"),
+    String(b"\"\"\""),
+    String(b"\""),
+    String(b"'"),
+];
+
+#[rustfmt::skip]
+const RACKET: [SyntaxRule; 4] = [
+    // `#\;` and `#\"` are character literals
+    Unescaped(&LineComment(b";")),
+    NestedBlockComment(b"#|", b"|#"),
+    HereDoc(b"#<<"),
+    Unescaped(&String(b"\"")),
+];
+
+// Embedded comments, e.g. `#`(...)`, are not supported, as their
+// brackets nest and conflict with `#` line comments
+const RAKU: [SyntaxRule; 3] = [
+    AtColumn(0, &BlockComment(b"=begin pod", b"=end pod")),
+    LineComment(b"#"),
+    String(b"\""),
+];
+
+const RUBY: [SyntaxRule; 5] = [
+    Shebang,
+    AtColumn(0, &BlockComment(b"=begin", b"=end")),
+    LineComment(b"#"),
+    String(b"\""),
+    String(b"'"),
+];
+
+const RUST: [SyntaxRule; 7] = [
     LineComment(b"//!"),
     LineComment(b"///"),
     LineComment(b"//"),
     BlockComment(b"/*", b"*/"),
     String(b"\""),
+    RawString(b"r"),
+    Char(b"'"),
 ];
 
 #[rustfmt::skip]
-const SHELL: [SyntaxRule; 3] = [
+const SHELL: [SyntaxRule; 8] = [
+    Shebang,
+    // `#` only starts a comment at the start of a word,
+    // e.g. `${x#foo}` and `$#` are not comments
+    PrecededBy(b" \t;(", &LineComment(b"#")),
+    DelimitedString(b"$'", b"'"),
+    // Command substitutions are skipped like strings, such that
+    // e.g. `$(foo #bar)` and `` `date # x` `` contain no comments
+    NestedString(b"$(", b")"),
+    Unescaped(&String(b"`")),
+    // `\"` and `\'` outside of strings are literal quotes
+    Unescaped(&String(b"\"")),
+    Unescaped(&String(b"'")),
+    ShellHereDoc(b"<<"),
+];
+
+const SMALI: [SyntaxRule; 2] = [LineComment(b"#"), String(b"\"")];
+
+const THRIFT: [SyntaxRule; 4] = [
+    LineComment(b"//"),
     LineComment(b"#"),
+    BlockComment(b"/*", b"*/"),
+    String(b"\""),
+];
+
+const V: [SyntaxRule; 5] = [
+    LineComment(b"//"),
+    NestedBlockComment(b"/*", b"*/"),
     String(b"\""),
     String(b"'"),
+    String(b"`"),
+];
+
+const VELOCITY: [SyntaxRule; 3] = [
+    LineComment(b"##"),
+    BlockComment(b"#*", b"*#"),
+    BlockComment(b"<!--", b"-->"),
 ];
 
-// The array is sorted by the language name
-const SYNTAXES: [(&str, &[SyntaxRule]); 15] = [
+// The array is sorted by the language name. It's a `static`, such
+// that the rules returned by `get_syntax` have a unique address, which
+// `precomputed_matcher` and `default_trim_leading_space` rely on.
+static SYNTAXES: [(&str, &[SyntaxRule]); 32] = [
+    ("asn1", &ASN1),
+    ("bibtex", &BIBTEX),
     ("c", &C),
-    ("cpp", &C),
-    ("css", &C),
-    ("glsl", &C),
-    ("java", &C),
-    ("javascript", &C),
-    ("json", &C),
-    ("jsonc", &C),
+    ("capnp", &CAPNP),
+    ("cobol", &COBOL),
+    ("cython", &CYTHON),
+    ("django", &DJANGO),
+    ("freemarker", &FREEMARKER),
+    ("gherkin", &GHERKIN),
+    ("haskell", &HASKELL),
+    ("ini", &INI),
+    ("jai", &JAI),
+    ("javascript", &JAVASCRIPT),
+    ("json5", &JSON5),
+    ("less", &LESS),
+    ("llvm", &LLVM_IR),
+    ("markdown", &MARKDOWN),
+    ("matlab", &MATLAB),
+    ("mermaid", &MERMAID),
+    ("odin", &ODIN),
+    ("plantuml", &PLANTUML),
+    ("postscript", &POSTSCRIPT),
     ("python", &PYTHON),
+    ("racket", &RACKET),
+    ("raku", &RAKU),
+    ("ruby", &RUBY),
     ("rust", &RUST),
-    ("scss", &C),
     ("shell", &SHELL),
-    ("toml", &C),
-    ("typescript", &C),
-    ("yaml", &C),
+    ("smali", &SMALI),
+    ("thrift", &THRIFT),
+    ("v", &V),
+    ("velocity", &VELOCITY),
+];
+
+// Languages whose comments conventionally have a space after the
+// delimiter, which is trimmed from the text by default. The array is
+// sorted by the language name
+const TRIM_LEADING_SPACE: [&str; 1] = ["gherkin"];
+
+// Languages whose block comment lines are conventionally prefixed,
+// e.g. ` * Foo`. The array is sorted by the language name
+const BLOCK_PREFIXES: [(&str, &str); 9] = [
+    ("c", "*"),
+    ("jai", "*"),
+    ("javascript", "*"),
+    ("json5", "*"),
+    ("less", "*"),
+    ("odin", "*"),
+    ("rust", "*"),
+    ("thrift", "*"),
+    ("v", "*"),
+];
+
+/// Returns the block comment line prefix of `rules`, if `rules` are
+/// the rules of a predefined language with such a convention.
+pub(crate) fn default_block_prefix(rules: &[SyntaxRule]) -> Option<&'static str> {
+    let &(name, _) = SYNTAXES
+        .iter()
+        .find(|&&(_, syntax)| std::ptr::eq(syntax, rules))?;
+    BLOCK_PREFIXES
+        .binary_search_by_key(&name, |&(name, _)| name)
+        .ok()
+        .map(|i| BLOCK_PREFIXES[i].1)
+}
+
+/// Returns `true` if `rules` are the rules of a predefined language,
+/// whose comments have a leading space trimmed by default.
+pub(crate) fn default_trim_leading_space(rules: &[SyntaxRule]) -> bool {
+    SYNTAXES
+        .iter()
+        .find(|&&(_, syntax)| std::ptr::eq(syntax, rules))
+        .is_some_and(|&(name, _)| TRIM_LEADING_SPACE.binary_search(&name).is_ok())
+}
+
+// The first-byte jump tables of `SYNTAXES`, where `MATCHERS[i][b]`
+// is `true` if any rule in `SYNTAXES[i]` can start with byte `b`.
+#[cfg(feature = "precompute")]
+static MATCHERS: [[bool; 256]; SYNTAXES.len()] = first_byte_tables(&SYNTAXES);
+
+#[cfg(feature = "precompute")]
+const fn first_byte_tables<const N: usize>(
+    syntaxes: &[(&str, &[SyntaxRule]); N],
+) -> [[bool; 256]; N] {
+    let mut tables = [[false; 256]; N];
+    let mut i = 0;
+    while i < N {
+        let rules = syntaxes[i].1;
+        let mut j = 0;
+        while j < rules.len() {
+            if let Some(b) = rules[j].first_byte() {
+                tables[i][b as usize] = true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    tables
+}
+
+/// Returns the precomputed first-byte jump table of `rules`,
+/// if `rules` are the rules of a predefined language.
+#[cfg(feature = "precompute")]
+pub(crate) fn precomputed_matcher(rules: &[SyntaxRule]) -> Option<&'static [bool; 256]> {
+    SYNTAXES
+        .iter()
+        .position(|&(_, syntax)| std::ptr::eq(syntax, rules))
+        .map(|i| &MATCHERS[i])
+}
+
+// Languages which share the syntax rules of a canonical language
+// in `SYNTAXES`. The array is sorted by the alias
+const ALIASES: [(&str, &str); 20] = [
+    ("cpp", "c"),
+    ("css", "c"),
+    ("cucumber", "gherkin"),
+    ("flatbuffers", "c"),
+    ("ftl", "freemarker"),
+    ("glsl", "c"),
+    ("java", "c"),
+    ("json", "c"),
+    ("jsonc", "c"),
+    ("mib", "asn1"),
+    ("pdf", "postscript"),
+    ("perl6", "raku"),
+    ("pyrex", "cython"),
+    ("scheme", "racket"),
+    ("scss", "c"),
+    ("stylus", "less"),
+    ("toml", "c"),
+    ("typescript", "javascript"),
+    ("vtl", "velocity"),
+    ("yaml", "c"),
 ];
 
 /// Given a language name, get [syntax rules] for a predefined
@@ -87,12 +401,85 @@ const SYNTAXES: [(&str, &[SyntaxRule]); 15] = [
 /// [syntax rules]: enum.SyntaxRule.html
 #[inline]
 pub fn get_syntax<S: AsRef<str>>(name: S) -> Option<&'static [SyntaxRule<'static>]> {
+    let name = canonical_language(name)?;
     SYNTAXES
-        .binary_search_by_key(&name.as_ref(), |&(name, _)| name)
+        .binary_search_by_key(&name, |&(name, _)| name)
         .ok()
         .map(|i| SYNTAXES[i].1)
 }
 
+/// Returns the number of predefined languages included in the crate,
+/// excluding aliases such as `cpp` for `c`.
+///
+/// # Example
+///
+/// ```
+/// use comment_parser::language_count;
+///
+/// assert!(language_count() > 0);
+/// ```
+#[inline]
+pub fn language_count() -> usize {
+    SYNTAXES.len()
+}
+
+/// Returns an iterator over the name and [syntax rules] of each
+/// predefined language included in the crate, sorted by the name.
+/// Aliases such as `cpp` for `c` are excluded.
+///
+/// [syntax rules]: enum.SyntaxRule.html
+///
+/// # Example
+///
+/// ```
+/// use comment_parser::{get_syntax, iter_languages, language_count};
+///
+/// assert_eq!(iter_languages().count(), language_count());
+///
+/// for (name, rules) in iter_languages() {
+///     assert_eq!(get_syntax(name).map(<[_]>::len), Some(rules.len()));
+/// }
+/// ```
+#[inline]
+pub fn iter_languages() -> impl Iterator<Item = (&'static str, &'static [SyntaxRule<'static>])> {
+    SYNTAXES.iter().copied()
+}
+
+/// Given a language name, get the name of the canonical language,
+/// whose [syntax rules] the language shares. For instance, `cpp`,
+/// `java` and many other C-like languages share the rules of `c`.
+/// Returns `None` if the language is not supported.
+///
+/// Languages with the same canonical language are equivalent, when
+/// it comes to comments and strings. A canonical language is its
+/// own canonical language.
+///
+/// [syntax rules]: enum.SyntaxRule.html
+///
+/// # Example
+///
+/// ```
+/// use comment_parser::canonical_language;
+///
+/// assert_eq!(canonical_language("cpp"), Some("c"));
+/// assert_eq!(canonical_language("c"), Some("c"));
+/// assert_eq!(canonical_language("stylus"), Some("less"));
+/// assert_eq!(canonical_language("foo"), None);
+/// ```
+#[inline]
+pub fn canonical_language<S: AsRef<str>>(name: S) -> Option<&'static str> {
+    let name = name.as_ref();
+
+    if let Ok(i) = SYNTAXES.binary_search_by_key(&name, |&(name, _)| name) {
+        return Some(SYNTAXES[i].0);
+    }
+
+    ALIASES
+        .binary_search_by_key(&name, |&(alias, _)| alias)
+        .ok()
+        .map(|i| ALIASES[i].1)
+}
+
 /// Given a [`Path`], get [syntax rules] for a predefined
 /// language included in the crate.
 /// The language is identified from the [path extension], and
@@ -259,6 +646,58 @@ pub fn get_syntax_from_extension<S: AsRef<str>>(
     }
 }
 
+/// File names of lock files, which are generated and not worth
+/// scanning for comments, in sorted order.
+const GENERATED_FILE_NAMES: [&str; 8] = [
+    "cargo.lock",
+    "composer.lock",
+    "gemfile.lock",
+    "go.sum",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "yarn.lock",
+];
+
+/// File name suffixes of minified and generated files.
+const GENERATED_FILE_SUFFIXES: [&str; 9] = [
+    ".bundle.js",
+    ".designer.cs",
+    ".g.dart",
+    ".generated.cs",
+    ".map",
+    ".min.css",
+    ".min.js",
+    ".pb.go",
+    "_pb2.py",
+];
+
+/// Returns `true` if the file `name` looks like a minified or generated
+/// file, such as `app.min.js` or `package-lock.json`, which usually
+/// should not be scanned for comments.
+///
+/// Only the file name is checked, i.e. the last component of a path.
+/// The casing of `name` does not affect the result.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::should_skip_file;
+/// assert!(should_skip_file("app.min.js"));
+/// assert!(should_skip_file("web/package-lock.json"));
+///
+/// assert!(!should_skip_file("app.js"));
+/// ```
+pub fn should_skip_file(name: &str) -> bool {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let name = name.to_ascii_lowercase();
+
+    GENERATED_FILE_NAMES.binary_search(&name.as_str()).is_ok()
+        || GENERATED_FILE_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+}
+
 /// `LanguageError` is an error that can be returned by
 /// [`get_syntax_from_path`] and [`get_syntax_from_extension`].
 ///
@@ -326,4 +765,1017 @@ fn check_order() {
             b,
         );
     }
+
+    for (a, b) in ALIASES.iter().zip(ALIASES.iter().skip(1)) {
+        assert!(
+            a.0 < b.0,
+            "Aliases out of order - {:?} should come after {:?}",
+            a,
+            b,
+        );
+    }
+
+    for (a, b) in GENERATED_FILE_NAMES
+        .iter()
+        .zip(GENERATED_FILE_NAMES.iter().skip(1))
+    {
+        assert!(
+            a < b,
+            "Generated file names out of order - {:?} should come after {:?}",
+            a,
+            b,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canonical_language, get_syntax, get_syntax_from_extension, should_skip_file, ALIASES,
+        SYNTAXES,
+    };
+    use crate::{CommentParser, Event, EventKind, SyntaxRule};
+
+    #[test]
+    fn aliases() {
+        for &(alias, name) in ALIASES.iter() {
+            assert!(get_syntax(name).is_some(), "{} -> {}", alias, name);
+            assert!(
+                SYNTAXES.iter().all(|&(name, _)| name != alias),
+                "{} is both a language and an alias",
+                alias
+            );
+        }
+
+        let c = get_syntax("c").unwrap();
+        let cpp = get_syntax("cpp").unwrap();
+        assert!(std::ptr::eq(c, cpp));
+
+        assert_eq!(canonical_language("cpp"), Some("c"));
+        assert_eq!(canonical_language("c"), Some("c"));
+        assert_eq!(canonical_language("pyrex"), Some("cython"));
+        assert_eq!(canonical_language("c++"), None);
+    }
+
+    #[test]
+    fn no_conflicts() {
+        for &(name, rules) in SYNTAXES.iter() {
+            let conflicts = SyntaxRule::check_conflicts(rules);
+            assert!(conflicts.is_empty(), "{}: {:?}", name, conflicts);
+        }
+    }
+
+    #[test]
+    fn cobol() {
+        use Event::*;
+
+        let cobol = r#"
+      * Column 7 comment
+      / Column 7 page break comment
+       * Column 8 is code
+           COMPUTE X = A * B.
+           DISPLAY "*> Foo". *> Inline
+"#;
+
+        let rules = get_syntax("cobol").unwrap();
+        let comments = CommentParser::new(cobol, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                LineComment("      * Column 7 comment", " Column 7 comment", "*"),
+                LineComment(
+                    "      / Column 7 page break comment",
+                    " Column 7 page break comment",
+                    "/"
+                ),
+                LineComment("           DISPLAY \"*> Foo\". *> Inline", " Inline", "*>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn json5() {
+        use Event::*;
+
+        let json5 = r#"{
+  // Line
+  /* Block */
+  single: 'http://foo',
+  double: "/* bar */",
+  continued: 'Foo \
+// Baz',
+}"#;
+
+        let rules = get_syntax("json5").unwrap();
+        let comments = CommentParser::new(json5, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                LineComment("  // Line", " Line", "//"),
+                BlockComment("/* Block */", " Block "),
+            ]
+        );
+    }
+
+    #[test]
+    fn less_stylus() {
+        use Event::*;
+
+        let code = r#"
+// Silent
+/* Block */
+@import "//cdn.com/foo.css";
+a { background: url('//cdn.com/foo.png'); }
+"#;
+
+        for &name in ["less", "stylus"].iter() {
+            let rules = get_syntax(name).unwrap();
+            let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+
+            assert_eq!(
+                comments,
+                [
+                    LineComment("// Silent", " Silent", "//"),
+                    BlockComment("/* Block */", " Block "),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn less_unquoted_url() {
+        let code = "a { background: url(//cdn.com/foo.png); }";
+        let rules = get_syntax("less").unwrap();
+
+        let mut parser = CommentParser::new(code, rules);
+        assert_eq!(
+            parser.next(),
+            Some(Event::LineComment(code, "cdn.com/foo.png); }", "//"))
+        );
+    }
+
+    #[test]
+    fn django() {
+        use Event::*;
+
+        let code = r#"
+{% comment %}Foo {% if x %}{% endif %}{% endcomment %}
+{%- comment "Note" -%} Bar {%- endcomment -%}
+{%comment%}Baz{%  endcomment  %}
+{# Inline #} <!-- HTML -->
+{% if comment %}{{ comment }}{% endif %}
+{% commentary %}{% endcomment %}
+"#;
+
+        let rules = get_syntax("django").unwrap();
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                BlockComment(
+                    "{% comment %}Foo {% if x %}{% endif %}{% endcomment %}",
+                    "Foo {% if x %}{% endif %}",
+                ),
+                BlockComment("{%- comment \"Note\" -%} Bar {%- endcomment -%}", " Bar ",),
+                BlockComment("{%comment%}Baz{%  endcomment  %}", "Baz"),
+                BlockComment("{# Inline #}", " Inline "),
+                BlockComment("<!-- HTML -->", " HTML "),
+            ]
+        );
+    }
+
+    #[test]
+    fn django_whitespace_control() {
+        let code = "{%- comment -%} Foo {%- endcomment -%}";
+        let rules = get_syntax("django").unwrap();
+
+        let mut parser = CommentParser::new(code, rules);
+        assert_eq!(parser.next(), Some(Event::BlockComment(code, " Foo ")));
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn shell_word_boundary() {
+        let code = r#"
+# real
+echo ${x#foo}
+echo $#
+echo a # trailing
+(# grouped)
+"#;
+        let rules = get_syntax("shell").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, [" real", " trailing", " grouped)"]);
+    }
+
+    #[test]
+    fn cython() {
+        let code = r#"
+# Foo
+def foo():
+    """Not a # comment"""
+    return 0
+
+cdef int bar():
+    '''Also not a # comment'''
+    return 1  # Bar
+"#;
+        let rules = get_syntax("cython").unwrap();
+
+        let events = CommentParser::new(code, rules)
+            .include_strings(true)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            [
+                Event::LineComment("# Foo", " Foo", "#"),
+                Event::String("\"\"\"Not a # comment\"\"\"", "Not a # comment"),
+                Event::String("'''Also not a # comment'''", "Also not a # comment"),
+                Event::LineComment("    return 1  # Bar", " Bar", "#"),
+            ]
+        );
+
+        assert!(get_syntax("pyrex").is_some());
+    }
+
+    #[test]
+    fn python_line_joins() {
+        let code =
+            "a = 1 + \\\n2  # Foo\nb = \\\n    3\n# Bar\nc = \"\\\n# Not a comment\"  # Baz\n";
+        let rules = get_syntax("python").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| (e.raw(), e.text()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                ("2  # Foo", " Foo"),
+                ("# Bar", " Bar"),
+                ("# Not a comment\"  # Baz", " Baz"),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_line_join_in_comment() {
+        let code = "# Foo \\\n# Bar\nx = 1\n";
+        let rules = get_syntax("python").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, [" Foo \\", " Bar"]);
+    }
+
+    #[test]
+    fn postscript() {
+        let code = r#"%!PS-Adobe-3.0
+% Foo
+/Times-Roman findfont 12 scalefont setfont
+(a (b % c) \) % d) show % Bar
+%%EOF
+"#;
+        let rules = get_syntax("postscript").unwrap();
+
+        let events = CommentParser::new(code, rules)
+            .include_strings(true)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            [
+                Event::LineComment("%!PS-Adobe-3.0", "!PS-Adobe-3.0", "%"),
+                Event::LineComment("% Foo", " Foo", "%"),
+                Event::String(r"(a (b % c) \) % d)", r"a (b % c) \) % d"),
+                Event::LineComment(r"(a (b % c) \) % d) show % Bar", " Bar", "%"),
+                Event::LineComment("%%EOF", "%EOF", "%"),
+            ]
+        );
+    }
+
+    #[test]
+    fn plantuml() {
+        let code = r#"@startuml
+' Foo
+Alice -> Bob: Hello
+/' Bar
+   Baz '/
+@enduml
+"#;
+        let rules = get_syntax("plantuml").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("' Foo", " Foo", "'"),
+                Event::BlockComment("/' Bar\n   Baz '/", " Bar\n   Baz "),
+            ]
+        );
+    }
+
+    #[test]
+    fn mermaid() {
+        let code = "graph TD\n%% Foo\nA --> B\n";
+        let rules = get_syntax("mermaid").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(comments, [Event::LineComment("%% Foo", " Foo", "%%")]);
+    }
+
+    #[test]
+    fn asn1() {
+        let code = r#"
+-- Foo
+Bar ::= INTEGER -- a -- (0..255) -- b
+Baz ::= VisibleString ("--not a comment--")
+"#;
+        let rules = get_syntax("asn1").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("-- Foo", " Foo", "--"),
+                Event::BlockComment("-- a --", " a "),
+                Event::LineComment("Bar ::= INTEGER -- a -- (0..255) -- b", " b", "--"),
+            ]
+        );
+    }
+
+    #[test]
+    fn capnp() {
+        let code = r##"@0xdbb9ad1f14bf0b36;
+# Foo
+struct Person {
+  name @0 :Text = "# Not a comment"; # Bar
+}
+"##;
+        let rules = get_syntax("capnp").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar"]);
+    }
+
+    #[test]
+    fn flatbuffers() {
+        let code = "// Foo\ntable Monster { /* Bar */ name:string = \"// Baz\"; }\n";
+        let rules = get_syntax("flatbuffers").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar "]);
+    }
+
+    #[test]
+    fn thrift() {
+        let code = r##"
+// Foo
+# Bar
+/* Baz */
+struct User {
+  1: string name = "# // /* Not a comment", # Qux
+}
+"##;
+        let rules = get_syntax("thrift").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("// Foo", " Foo", "//"),
+                Event::LineComment("# Bar", " Bar", "#"),
+                Event::BlockComment("/* Baz */", " Baz "),
+                Event::LineComment(
+                    r##"  1: string name = "# // /* Not a comment", # Qux"##,
+                    " Qux",
+                    "#",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn raku() {
+        let code = r##"
+# Foo
+say "# Not a comment"; # Bar
+=begin pod
+Baz
+=end pod
+  =begin pod
+"##;
+        let rules = get_syntax("raku").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("# Foo", " Foo", "#"),
+                Event::LineComment("say \"# Not a comment\"; # Bar", " Bar", "#"),
+                Event::BlockComment("=begin pod\nBaz\n=end pod", "\nBaz\n"),
+            ]
+        );
+
+        assert!(get_syntax("perl6").is_some());
+    }
+
+    #[test]
+    fn haskell() {
+        let code = "-- Foo\nmain = putStrLn \"-- Bar\" {- Baz {- Qux -} -}\n";
+        let rules = get_syntax("haskell").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("-- Foo", " Foo", "--"),
+                Event::BlockComment("{- Baz {- Qux -} -}", " Baz {- Qux -} "),
+            ]
+        );
+    }
+
+    #[test]
+    fn velocity() {
+        use Event::*;
+
+        let code = r#"
+## Foo
+#set($x = 1) ## Bar
+#* Baz
+   Qux *#
+<!-- HTML -->
+#if($x)#end
+"#;
+
+        let rules = get_syntax("velocity").unwrap();
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                LineComment("## Foo", " Foo", "##"),
+                LineComment("#set($x = 1) ## Bar", " Bar", "##"),
+                BlockComment("#* Baz\n   Qux *#", " Baz\n   Qux "),
+                BlockComment("<!-- HTML -->", " HTML "),
+            ]
+        );
+    }
+
+    #[test]
+    fn freemarker() {
+        use Event::*;
+
+        let code = r#"
+<#-- Foo -->
+<#if x>${x}</#if> <!-- HTML -->
+"#;
+
+        let rules = get_syntax("freemarker").unwrap();
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                BlockComment("<#-- Foo -->", " Foo "),
+                BlockComment("<!-- HTML -->", " HTML "),
+            ]
+        );
+    }
+
+    #[test]
+    fn ini_escaped_semicolon() {
+        let code = r#"
+; Foo
+[section]
+key = a\;b
+key = a ; Bar
+key = a\\; Baz
+color = #fff
+# Qux
+"#;
+        let rules = get_syntax("ini").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, [" Foo", " Bar", " Baz", " Qux"]);
+        assert!(get_syntax_from_extension("ini").is_ok());
+    }
+
+    #[test]
+    fn markdown() {
+        use Event::*;
+
+        let code = r#"
+# Title
+
+Foo <!-- Bar --> and `<!-- Not a comment -->` and \`<!-- Baz -->\`
+
+``code with ` and <!-- Not a comment -->``
+
+```html
+<!-- Not a comment -->
+```
+
+[//]: # (Qux)
+"#;
+
+        let rules = get_syntax("markdown").unwrap();
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                BlockComment("<!-- Bar -->", " Bar "),
+                BlockComment("<!-- Baz -->", " Baz "),
+                LineComment("[//]: # (Qux)", " (Qux)", "[//]: #"),
+            ]
+        );
+        assert!(get_syntax_from_extension("md").is_ok());
+    }
+
+    #[test]
+    fn gherkin() {
+        let code = r#"
+# Foo
+@wip @slow
+Feature: Comments
+  # Bar
+  Scenario: Doc string
+    Given a file containing:
+      """
+      # Not a comment
+      """
+    And a step with #hash
+    Then it works
+"#;
+        let rules = get_syntax("gherkin").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, ["Foo", "Bar"]);
+        assert_eq!(canonical_language("cucumber"), Some("gherkin"));
+    }
+
+    #[test]
+    fn ruby() {
+        use Event::*;
+
+        let code = r##"
+# Foo
+puts "# Not a comment" # Bar
+=begin
+Baz
+=end
+  =begin
+"##;
+        let rules = get_syntax("ruby").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                LineComment("# Foo", " Foo", "#"),
+                LineComment("puts \"# Not a comment\" # Bar", " Bar", "#"),
+                BlockComment("=begin\nBaz\n=end", "\nBaz\n"),
+            ]
+        );
+
+        assert!(get_syntax_from_extension("rb").is_ok());
+    }
+
+    #[test]
+    fn matlab() {
+        use Event::*;
+
+        let code = r#"
+%{
+Foo
+%}
+x = [1 2]'; % Bar
+y = 1; %{ Baz %}
+"#;
+        let rules = get_syntax("matlab").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                BlockComment("%{\nFoo\n%}", "\nFoo\n"),
+                LineComment("x = [1 2]'; % Bar", " Bar", "%"),
+                LineComment("y = 1; %{ Baz %}", "{ Baz %}", "%"),
+            ]
+        );
+    }
+
+    #[test]
+    fn javascript_regex_literal() {
+        let code = r"
+const re = /a\/\/b/;
+const x = a / b / c; // Foo
+const y = f(/[/*]/g, 2); /* Bar */
+return /'/.test(s) // Baz
+const z = a++ / b-- / c; // Qux
+const el = <p>{(a) / 2}</p>; // Quux
+";
+        let rules = get_syntax("javascript").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("const x = a / b / c; // Foo", " Foo", "//"),
+                Event::BlockComment("/* Bar */", " Bar "),
+                Event::LineComment("return /'/.test(s) // Baz", " Baz", "//"),
+                Event::LineComment("const z = a++ / b-- / c; // Qux", " Qux", "//"),
+                Event::LineComment("const el = <p>{(a) / 2}</p>; // Quux", " Quux", "//"),
+            ]
+        );
+
+        let strings = CommentParser::new(code, rules)
+            .include_strings(true)
+            .filter(|event| event.kind() == EventKind::String)
+            .map(|event| event.text())
+            .collect::<Vec<_>>();
+        assert_eq!(strings, [r"a\/\/b", "[/*]", "'"]);
+    }
+
+    #[cfg(feature = "precompute")]
+    #[test]
+    fn precomputed_matchers() {
+        let mut delims = Vec::new();
+        for &(_, rules) in SYNTAXES.iter() {
+            for rule in rules {
+                match rule.inner() {
+                    SyntaxRule::BlockComment(start, end)
+                    | SyntaxRule::NestedBlockComment(start, end)
+                    | SyntaxRule::LineCommentUntil(start, end)
+                    | SyntaxRule::NestedString(start, end)
+                    | SyntaxRule::DelimitedString(start, end) => delims.extend(&[*start, *end]),
+                    rule => delims.push(rule.start()),
+                }
+            }
+        }
+
+        let mut code = String::from("#!/bin/sh\n");
+        for delim in &delims {
+            let delim = std::str::from_utf8(delim).unwrap();
+            code.push_str(&format!("a {} b = /c/ {0}\n{0}d\n", delim));
+        }
+        for delim in &delims {
+            code.push_str(std::str::from_utf8(delim).unwrap());
+        }
+
+        for &(name, rules) in SYNTAXES.iter() {
+            // A copy of the rules is not a predefined language,
+            // so it's parsed without a precomputed matcher
+            let copy = rules.to_vec();
+            assert!(super::precomputed_matcher(rules).is_some());
+            assert!(super::precomputed_matcher(&copy).is_none());
+
+            let parse = |rules| {
+                CommentParser::new(&code, rules)
+                    .include_strings(true)
+                    .shebang(true)
+                    .trim_leading_space(false)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(parse(rules), parse(&copy), "{}", name);
+        }
+    }
+
+    #[test]
+    fn language_count() {
+        assert_eq!(super::language_count(), SYNTAXES.len());
+        assert!(SYNTAXES.iter().all(|&(name, _)| get_syntax(name).is_some()));
+    }
+
+    #[test]
+    fn racket() {
+        let code = r#"
+#lang racket
+; Foo
+(define s #<<END
+; Not a comment
+"Not a string
+END
+)
+#| Bar #| Baz |# Qux |#
+(display #\;) ; Quux
+"#;
+        let rules = get_syntax("racket").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("; Foo", " Foo", ";"),
+                Event::BlockComment("#| Bar #| Baz |# Qux |#", " Bar #| Baz |# Qux "),
+                Event::LineComment(r"(display #\;) ; Quux", " Quux", ";"),
+            ]
+        );
+
+        let strings = CommentParser::new(code, rules)
+            .include_strings(true)
+            .filter(|event| event.is_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            strings,
+            [Event::String(
+                "#<<END\n; Not a comment\n\"Not a string\nEND",
+                "; Not a comment\n\"Not a string"
+            )]
+        );
+    }
+
+    #[test]
+    fn bibtex() {
+        let code = r#"
+% Foo
+@comment{Bar}
+@book{knuth,
+  title = {100% {Baz}},
+  note = "50% Qux",
+}
+"#;
+        let rules = get_syntax("bibtex").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::LineComment("% Foo", " Foo", "%"),
+                Event::BlockComment("@comment{Bar}", "Bar"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mib() {
+        let code = r#"
+sysDescr OBJECT-TYPE -- Foo -- SYNTAX DisplayString
+    DESCRIPTION "-- Not a comment --"
+    ::= { system 1 } -- Bar
+"#;
+        let rules = get_syntax("mib").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::BlockComment("-- Foo --", " Foo "),
+                Event::LineComment("    ::= { system 1 } -- Bar", " Bar", "--"),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_trim_leading_space() {
+        let code = "#  Foo\n";
+        let text = |rules| CommentParser::new(code, rules).next().unwrap().text();
+
+        let gherkin = get_syntax("gherkin").unwrap();
+        let copy = gherkin.to_vec();
+        assert_eq!(text(gherkin), " Foo");
+        assert_eq!(text(&copy), "  Foo");
+
+        let python = get_syntax("python").unwrap();
+        assert_eq!(text(python), "  Foo");
+    }
+
+    #[test]
+    fn nested_block_comments() {
+        let code = r#"
+/* Foo /* Bar */ Baz */
+main :: proc() {} // Qux
+s := "/* Not a comment"
+"#;
+
+        for &name in &["odin", "jai", "v"] {
+            let rules = get_syntax(name).unwrap();
+
+            let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+            assert_eq!(
+                comments,
+                [
+                    Event::BlockComment("/* Foo /* Bar */ Baz */", " Foo /* Bar */ Baz "),
+                    Event::LineComment("main :: proc() {} // Qux", " Qux", "//"),
+                ],
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn v_raw_string() {
+        let code = "s := `// Not a comment` // Foo\n";
+        let rules = get_syntax("v").unwrap();
+
+        let comments = CommentParser::new(code, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [Event::LineComment(code.trim_end(), " Foo", "//")]
+        );
+    }
+
+    #[test]
+    fn shell_here_doc() {
+        let code = "
+# Foo
+cat <<EOF > out.txt
+# Not a comment
+EOF
+cat <<-'END'
+\t# Not a comment either
+\tEND
+grep x <<< \"y\" # Bar
+";
+        let rules = get_syntax("shell").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar"]);
+
+        let strings = CommentParser::new(code, rules)
+            .include_strings(true)
+            .filter(|e| e.is_string())
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            strings,
+            ["# Not a comment", "\t# Not a comment either", "y"]
+        );
+
+        // Left shifts are not here-documents
+        let code = "
+(( y = x << 2 )) # Foo
+echo $(( 1 << n )) # Bar
+cat<<EOF
+# Not a comment
+EOF
+# Baz
+";
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar", " Baz"]);
+    }
+
+    #[test]
+    fn racket_here_doc_tags() {
+        let code =
+            "#<<END OF TEXT\n; Not a comment\nEND OF TEXT\n#<<-x\n; Not a comment\n-x\n; Foo\n";
+        let rules = get_syntax("racket").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo"]);
+    }
+
+    #[test]
+    fn shell_ansi_c_string() {
+        let code = r"
+echo $'it\'s' # Foo
+echo 'it'\''s' # Bar
+";
+        let rules = get_syntax("shell").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar"]);
+
+        let strings = CommentParser::new(code, rules)
+            .include_strings(true)
+            .filter(|e| e.is_string())
+            .map(|e| e.raw())
+            .collect::<Vec<_>>();
+        assert_eq!(strings, [r"$'it\'s'", "'it'", "'s'"]);
+    }
+
+    #[test]
+    fn shell_command_substitution() {
+        let code = r#"
+echo `date # x` # Foo
+x=$(foo #bar) # Bar
+y="$(echo $(baz #qux))" # Baz
+echo \`not # Quux
+"#;
+        let rules = get_syntax("shell").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar", " Baz", " Quux"]);
+
+        let strings = CommentParser::new(code, rules)
+            .include_strings(true)
+            .filter(|e| e.is_string())
+            .map(|e| e.raw())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            strings,
+            ["`date # x`", "$(foo #bar)", "\"$(echo $(baz #qux))\""]
+        );
+    }
+
+    #[test]
+    fn smali() {
+        let code = r#"
+# Foo
+.method public static main([Ljava/lang/String;)V
+    const-string v0, "Not a # comment" # Bar
+.end method
+"#;
+        let rules = get_syntax("smali").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar"]);
+    }
+
+    #[test]
+    fn llvm_ir() {
+        let code = r#"
+; Foo
+@str = private constant [16 x i8] c"Not a ; comment\00" ; Bar
+define i32 @main() {
+  ret i32 0 ; Baz
+}
+"#;
+        let rules = get_syntax("llvm").unwrap();
+
+        let comments = CommentParser::new(code, rules)
+            .map(|e| e.text())
+            .collect::<Vec<_>>();
+        assert_eq!(comments, [" Foo", " Bar", " Baz"]);
+    }
+
+    #[test]
+    fn block_prefixes() {
+        let mut names = super::BLOCK_PREFIXES.iter().map(|&(name, _)| name);
+        assert!(names.all(|name| get_syntax(name).is_some()));
+        assert!(super::BLOCK_PREFIXES.windows(2).all(|w| w[0].0 < w[1].0));
+
+        let code = "/*\n * Foo\n *   Bar\n */";
+
+        let c = get_syntax("c").unwrap();
+        let texts = CommentParser::new(code, c)
+            .clean_texts()
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["\nFoo\n  Bar\n"]);
+
+        let asn1 = get_syntax("asn1").unwrap();
+        let texts = CommentParser::new(code, asn1)
+            .clean_texts()
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["\n * Foo\n *   Bar\n "]);
+    }
+
+    #[test]
+    fn iter_languages() {
+        let languages = super::iter_languages().collect::<Vec<_>>();
+        assert_eq!(languages.len(), SYNTAXES.len());
+        assert!(languages.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(languages
+            .iter()
+            .all(|&(_, rules)| SyntaxRule::check_rules(rules)));
+    }
+
+    #[test]
+    fn should_skip_generated() {
+        assert!(should_skip_file("app.min.js"));
+        assert!(should_skip_file("static/css/App.MIN.css"));
+        assert!(should_skip_file("package-lock.json"));
+        assert!(should_skip_file("C:\\project\\Cargo.lock"));
+        assert!(should_skip_file("api_pb2.py"));
+
+        assert!(!should_skip_file("app.js"));
+        assert!(!should_skip_file("min.js"));
+        assert!(!should_skip_file("package.json"));
+        assert!(!should_skip_file("package-lock.json/main.rs"));
+    }
 }
@@ -68,10 +68,51 @@
 #![deny(missing_debug_implementations)]
 #![warn(clippy::all)]
 
+mod adapters;
+mod archive;
+mod binary;
+mod convert;
+mod custom;
+mod diff;
+mod incremental;
 mod languages;
+mod literate;
+mod multi;
 mod parse;
+mod patch;
+mod report;
+mod source;
+mod stream;
 mod syntax;
+mod validate;
 
-pub use languages::{get_syntax, get_syntax_from_extension, get_syntax_from_path, LanguageError};
-pub use parse::{CommentParser, Event};
-pub use syntax::SyntaxRule;
+pub use adapters::{
+    CommentNode, DocGroup, Handlers, LspPosition, PrecedingToken, Segment, Token, TokenKind,
+};
+pub use archive::parse_archive;
+pub use binary::{decode_comments, encode_comments};
+pub use convert::{convert_comments, strip_comments, strip_comments_of_kind, StripOptions};
+pub use custom::{CustomMatcher, MatchResult};
+pub use diff::{diff_comments, CommentDiff};
+pub use incremental::IncrementalCommentParser;
+pub use languages::{
+    canonical_language, get_syntax, get_syntax_from_extension, get_syntax_from_path,
+    iter_languages, language_count, should_skip_file, LanguageError,
+};
+pub use literate::LiterateParser;
+pub use multi::{MultiLanguageParser, Region};
+pub use parse::{
+    Annotation, BytesScanned, Category, ColumnMode, CommentParser, Diagnostic, Event, EventKind,
+    ParseError, ParserState, PendingState,
+};
+pub use patch::{DiffComment, DiffCommentParser, DiffLine};
+pub use report::{
+    comments_to_csv, dominant_comment_style, marker_histogram, report_comments, StyleMetric,
+};
+pub use source::{
+    comments_from_encoded, comments_in_range, copyright_years, doc_for_identifier,
+    doc_for_identifier_with, extract_license_header, parse_source, Encoding, OwnedEvent, Source,
+};
+pub use stream::{AsyncCommentParser, ChunkStream, NextComment};
+pub use syntax::{capabilities, Capabilities, Conflict, SyntaxError, SyntaxRule};
+pub use validate::{validate_rules_against, Issue};
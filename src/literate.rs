@@ -0,0 +1,221 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+use std::vec::IntoIter;
+
+use line_span::LineSpanExt;
+
+use crate::parse::{CommentParser, Event};
+use crate::syntax::SyntaxRule;
+
+enum Segment<'a> {
+    Prose(Event<'a>),
+    Code(Box<CommentParser<'a>>),
+}
+
+/// `LiterateParser` parses literate source code, such as literate
+/// Haskell (`.lhs`), where the prose is the comment and the code is
+/// marked as such.
+///
+/// Code is either a line starting with `>` (bird-track style), or
+/// the lines between a `\begin{code}` and `\end{code}` line (LaTeX
+/// style). All remaining lines are prose.
+///
+/// Each paragraph of prose, i.e. consecutive non-blank lines, is
+/// produced as an [`Event::BlockComment`], where the raw text and
+/// the text are the same. The code is parsed based on `rules`, and
+/// its comments are produced in source order along with the prose.
+///
+/// [`Event::BlockComment`]: enum.Event.html#variant.BlockComment
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, Event, LiterateParser};
+/// let lhs = "Foo\n\n> main = print 1 -- Bar\n\nBaz\n";
+/// let rules = get_syntax("haskell").unwrap();
+///
+/// let comments = LiterateParser::new(lhs, rules).collect::<Vec<_>>();
+/// assert_eq!(
+///     comments,
+///     [
+///         Event::BlockComment("Foo", "Foo"),
+///         Event::LineComment("> main = print 1 -- Bar", " Bar", "--"),
+///         Event::BlockComment("Baz", "Baz"),
+///     ]
+/// );
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct LiterateParser<'a> {
+    segments: IntoIter<Segment<'a>>,
+    current: Option<CommentParser<'a>>,
+}
+
+impl<'a> LiterateParser<'a> {
+    /// Creates a `LiterateParser` which parses `text`, where the
+    /// code is parsed based on `rules`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`SyntaxRule`] contains an empty `&[u8]`.
+    ///
+    /// [`SyntaxRule`]: enum.SyntaxRule.html
+    pub fn new(text: &'a str, rules: &'a [SyntaxRule<'a>]) -> Self {
+        let mut segments = Vec::new();
+
+        let mut prose: Option<Range<usize>> = None;
+        let mut bird: Option<Range<usize>> = None;
+        let mut latex: Option<usize> = None;
+
+        for span in text.line_spans() {
+            let line = span.as_str();
+
+            if let Some(start) = latex {
+                if line.trim_end() == "\\end{code}" {
+                    let code = &text[start..span.start()];
+                    segments.push(Segment::Code(Box::new(CommentParser::new(code, rules))));
+                    latex = None;
+                }
+                continue;
+            }
+
+            if is_bird_track(line) {
+                push_prose(&mut segments, text, prose.take());
+                bird = Some(bird.map_or(span.range(), |r| r.start..span.end()));
+                continue;
+            }
+
+            if let Some(range) = bird.take() {
+                segments.push(Segment::Code(Box::new(CommentParser::new(
+                    &text[range],
+                    rules,
+                ))));
+            }
+
+            if line.trim().is_empty() {
+                push_prose(&mut segments, text, prose.take());
+            } else if line.trim_end() == "\\begin{code}" {
+                push_prose(&mut segments, text, prose.take());
+                latex = Some(span.range_with_ending().end);
+            } else {
+                prose = Some(prose.map_or(span.range(), |r| r.start..span.end()));
+            }
+        }
+
+        push_prose(&mut segments, text, prose);
+
+        if let Some(range) = bird {
+            segments.push(Segment::Code(Box::new(CommentParser::new(
+                &text[range],
+                rules,
+            ))));
+        }
+
+        // An unterminated `\begin{code}` extends to the end of the text
+        if let Some(start) = latex {
+            segments.push(Segment::Code(Box::new(CommentParser::new(
+                &text[start..],
+                rules,
+            ))));
+        }
+
+        Self {
+            segments: segments.into_iter(),
+            current: None,
+        }
+    }
+}
+
+/// Returns `true` if `line` is bird-track code, i.e. it starts
+/// with `>` followed by whitespace or the end of the line.
+#[inline]
+fn is_bird_track(line: &str) -> bool {
+    line.starts_with('>') && !line[1..].starts_with(|c: char| !c.is_whitespace())
+}
+
+#[inline]
+fn push_prose<'a>(segments: &mut Vec<Segment<'a>>, text: &'a str, prose: Option<Range<usize>>) {
+    if let Some(range) = prose {
+        let prose = &text[range];
+        segments.push(Segment::Prose(Event::BlockComment(prose, prose)));
+    }
+}
+
+impl<'a> Iterator for LiterateParser<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(parser) = &mut self.current {
+                if let Some(event) = parser.next() {
+                    return Some(event);
+                }
+                self.current = None;
+            }
+
+            match self.segments.next()? {
+                Segment::Prose(event) => return Some(event),
+                Segment::Code(parser) => self.current = Some(*parser),
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for LiterateParser<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_syntax, Event, LiterateParser};
+
+    #[test]
+    fn bird_track() {
+        let lhs = "
+Foo
+Bar
+
+> -- Baz
+> main :: IO ()
+>
+> main = putStrLn \"-- Not a comment\"
+Qux
+>not code -- Quux
+";
+        let rules = get_syntax("haskell").unwrap();
+
+        let comments = LiterateParser::new(lhs, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::BlockComment("Foo\nBar", "Foo\nBar"),
+                Event::LineComment("> -- Baz", " Baz", "--"),
+                Event::BlockComment("Qux\n>not code -- Quux", "Qux\n>not code -- Quux"),
+            ]
+        );
+    }
+
+    #[test]
+    fn latex_code() {
+        let lhs = r"Foo
+\begin{code}
+main = putStrLn {- Bar -} greeting
+  -- Baz
+\end{code}
+Qux
+
+\begin{code}
+-- Quux
+";
+        let rules = get_syntax("haskell").unwrap();
+
+        let comments = LiterateParser::new(lhs, rules).collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            [
+                Event::BlockComment("Foo", "Foo"),
+                Event::BlockComment("{- Bar -}", " Bar "),
+                Event::LineComment("  -- Baz", " Baz", "--"),
+                Event::BlockComment("Qux", "Qux"),
+                Event::LineComment("-- Quux", " Quux", "--"),
+            ]
+        );
+    }
+}
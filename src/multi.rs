@@ -0,0 +1,244 @@
+use std::iter::FusedIterator;
+use std::vec::IntoIter;
+
+use crate::languages::get_syntax;
+use crate::parse::{CommentParser, Event};
+use crate::syntax::SyntaxRule;
+
+/// `Region` is a region of text in another language, delimited by
+/// an `open` and `close` token, such as a fenced code block in
+/// Markdown, used by [`MultiLanguageParser`].
+///
+/// [`MultiLanguageParser`]: struct.MultiLanguageParser.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::Region;
+/// const RUST: Region = Region::new("```rust", "```", "rust");
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Region<'a> {
+    /// The token opening the region, e.g. `` ```rust ``.
+    pub open: &'a str,
+    /// The token closing the region, e.g. `` ``` ``.
+    pub close: &'a str,
+    /// The name of the language, as accepted by [`get_syntax`].
+    ///
+    /// [`get_syntax`]: fn.get_syntax.html
+    pub language: &'a str,
+}
+
+impl<'a> Region<'a> {
+    /// Creates a `Region` in `language`, delimited by `open` and `close`.
+    #[inline]
+    pub const fn new(open: &'a str, close: &'a str, language: &'a str) -> Self {
+        Self {
+            open,
+            close,
+            language,
+        }
+    }
+}
+
+/// `MultiLanguageParser` parses text containing [`Region`]s in other
+/// languages, where each region is parsed with the [syntax rules] of
+/// its language, and the remaining text with the markup rules.
+///
+/// Events are produced in source order as `(language, event)`, where
+/// `language` is `None` for events in the markup. Regions of a language
+/// without predefined [syntax rules] produce no events.
+///
+/// The tokens of a region are not part of any region, and an `open`
+/// token must be followed by whitespace or the end of the text. An
+/// unclosed region extends to the end of the text.
+///
+/// [`Region`]: struct.Region.html
+/// [syntax rules]: enum.SyntaxRule.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{MultiLanguageParser, Region, SyntaxRule};
+/// let markdown = "<!-- Foo -->\n```rust\n// Bar\n```\n```python\n# Baz\n```\n";
+///
+/// let rules = [SyntaxRule::BlockComment(b"<!--", b"-->")];
+/// let regions = [
+///     Region::new("```rust", "```", "rust"),
+///     Region::new("```python", "```", "python"),
+/// ];
+///
+/// let comments = MultiLanguageParser::new(markdown, &rules, &regions)
+///     .map(|(language, event)| (language, event.text()))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     comments,
+///     [
+///         (None, " Foo "),
+///         (Some("rust"), " Bar"),
+///         (Some("python"), " Baz"),
+///     ]
+/// );
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct MultiLanguageParser<'a> {
+    segments: IntoIter<(Option<&'a str>, CommentParser<'a>)>,
+    current: Option<(Option<&'a str>, CommentParser<'a>)>,
+}
+
+impl<'a> MultiLanguageParser<'a> {
+    /// Creates a `MultiLanguageParser` which parses `text` based on
+    /// the markup `rules`, and the `regions` based on their language.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`SyntaxRule`] contains an empty `&[u8]`, or
+    /// if the `open` or `close` token of any [`Region`] is empty.
+    ///
+    /// [`SyntaxRule`]: enum.SyntaxRule.html
+    /// [`Region`]: struct.Region.html
+    pub fn new(text: &'a str, rules: &'a [SyntaxRule<'a>], regions: &[Region<'a>]) -> Self {
+        assert!(
+            regions
+                .iter()
+                .all(|region| !region.open.is_empty() && !region.close.is_empty()),
+            "empty region token"
+        );
+
+        let mut segments = Vec::new();
+        let mut index = 0;
+
+        while let Some((start, region)) = find_region(text, index, regions) {
+            segments.push((None, CommentParser::new(&text[index..start], rules)));
+
+            let body = start + region.open.len();
+            let (end, after) = text[body..]
+                .find(region.close)
+                .map_or((text.len(), text.len()), |i| {
+                    (body + i, body + i + region.close.len())
+                });
+
+            if let Some(rules) = get_syntax(region.language) {
+                segments.push((
+                    Some(region.language),
+                    CommentParser::new(&text[body..end], rules),
+                ));
+            }
+
+            index = after;
+        }
+
+        segments.push((None, CommentParser::new(&text[index..], rules)));
+
+        Self {
+            segments: segments.into_iter(),
+            current: None,
+        }
+    }
+}
+
+/// Returns the byte index and region of the first `open` token at
+/// or after `index`, which is followed by whitespace or the end.
+fn find_region<'r, 'a>(
+    text: &str,
+    index: usize,
+    regions: &'r [Region<'a>],
+) -> Option<(usize, &'r Region<'a>)> {
+    regions
+        .iter()
+        .filter_map(|region| {
+            let mut from = index;
+            while let Some(i) = text[from..].find(region.open) {
+                let start = from + i;
+                let end = start + region.open.len();
+                if !text[end..].starts_with(|c: char| !c.is_whitespace()) {
+                    return Some((start, region));
+                }
+                from = end;
+            }
+            None
+        })
+        .min_by_key(|&(start, _)| start)
+}
+
+impl<'a> Iterator for MultiLanguageParser<'a> {
+    type Item = (Option<&'a str>, Event<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((language, parser)) = &mut self.current {
+                if let Some(event) = parser.next() {
+                    return Some((*language, event));
+                }
+            }
+
+            self.current = Some(self.segments.next()?);
+        }
+    }
+}
+
+impl<'a> FusedIterator for MultiLanguageParser<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Event, MultiLanguageParser, Region, SyntaxRule};
+
+    const MARKDOWN: [SyntaxRule; 1] = [SyntaxRule::BlockComment(b"<!--", b"-->")];
+
+    const REGIONS: [Region; 3] = [
+        Region::new("```rust", "```", "rust"),
+        Region::new("```python", "```", "python"),
+        Region::new("```text", "```", "text"),
+    ];
+
+    #[test]
+    fn markdown_fences() {
+        let markdown = r##"
+# Title
+
+<!-- Foo -->
+
+```rust
+// Bar
+let s = "# Not a comment";
+```
+
+```python
+# Baz
+s = "// Not a comment"
+```
+
+```rustc
+<!-- Qux -->
+```
+
+```text
+# Not a comment
+```
+"##;
+
+        let comments = MultiLanguageParser::new(markdown, &MARKDOWN, &REGIONS).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                (None, Event::BlockComment("<!-- Foo -->", " Foo ")),
+                (Some("rust"), Event::LineComment("// Bar", " Bar", "//")),
+                (Some("python"), Event::LineComment("# Baz", " Baz", "#")),
+                (None, Event::BlockComment("<!-- Qux -->", " Qux ")),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclosed_region() {
+        let markdown = "<!-- Foo -->\n```python\n# Bar\n<!-- Baz -->\n";
+
+        let comments = MultiLanguageParser::new(markdown, &MARKDOWN, &REGIONS)
+            .map(|(language, event)| (language, event.text()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, [(None, " Foo "), (Some("python"), " Bar")]);
+    }
+}
@@ -0,0 +1,365 @@
+use std::iter::FusedIterator;
+use std::vec::IntoIter;
+
+use crate::languages::get_syntax_from_path;
+use crate::parse::{range_in, CommentParser};
+use crate::source::OwnedEvent;
+use crate::syntax::SyntaxRule;
+
+/// `DiffLine` is the kind of line in a unified diff hunk, that a
+/// [`DiffComment`] starts on.
+///
+/// [`DiffComment`]: struct.DiffComment.html
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DiffLine {
+    /// A line prefixed with `+`, which only exists in the new file.
+    Added,
+    /// A line prefixed with `-`, which only exists in the old file.
+    Removed,
+    /// A line prefixed with ` `, which exists in both files.
+    Context,
+}
+
+/// `DiffComment` is a comment in a unified diff, produced by
+/// [`DiffCommentParser`].
+///
+/// [`DiffCommentParser`]: struct.DiffCommentParser.html
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DiffComment<'a> {
+    /// The path of the file, from the `+++` header, or the `---` header
+    /// if the file was deleted. A leading `b/` or `a/` is removed.
+    pub path: &'a str,
+    /// The kind of line the comment starts on.
+    pub line_kind: DiffLine,
+    /// The 1-based line number the comment starts on, in the new file,
+    /// or in the old file if the comment was removed.
+    pub line: usize,
+    /// The comment, without the diff markers. The ranges are byte
+    /// ranges in the code of the hunk, i.e. in the lines of the new
+    /// file, or of the old file if the comment was removed.
+    pub event: OwnedEvent,
+}
+
+/// `DiffCommentParser` parses the comments in a unified diff, such as
+/// the output of `git diff`.
+///
+/// The language of each file is detected from the path in its `+++`
+/// header, using [`get_syntax_from_path`]. Files in an unknown or
+/// unsupported language are skipped.
+///
+/// Each hunk is parsed with its diff markers removed, where added and
+/// context lines are parsed as the new file, and removed and context
+/// lines as the old file. Comments starting on context lines are only
+/// produced once. Since the code between hunks is unknown, each hunk
+/// is parsed separately.
+///
+/// [`get_syntax_from_path`]: fn.get_syntax_from_path.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{DiffCommentParser, DiffLine};
+/// let diff = "\
+/// --- a/main.rs
+/// +++ b/main.rs
+/// @@ -1,2 +1,2 @@
+/// -// Foo
+/// +// Bar
+///  fn main() {}
+/// ";
+///
+/// let comments = DiffCommentParser::new(diff)
+///     .map(|comment| (comment.line_kind, comment.line, comment.event.text))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     comments,
+///     [
+///         (DiffLine::Removed, 1, " Foo".to_string()),
+///         (DiffLine::Added, 1, " Bar".to_string()),
+///     ]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct DiffCommentParser<'a> {
+    comments: IntoIter<DiffComment<'a>>,
+}
+
+impl<'a> DiffCommentParser<'a> {
+    /// Creates a `DiffCommentParser` which parses the unified `diff`.
+    pub fn new(diff: &'a str) -> Self {
+        let mut comments = Vec::new();
+
+        let mut old_path = "";
+        let mut file: Option<(&'a str, &'static [SyntaxRule<'static>])> = None;
+        let mut hunk: Option<Hunk> = None;
+
+        for line in diff.lines() {
+            if let Some(h) = &mut hunk {
+                if h.push(line) {
+                    if h.is_done() {
+                        if let (Some(h), Some((path, rules))) = (hunk.take(), file) {
+                            h.parse(path, rules, &mut comments);
+                        }
+                    }
+                    continue;
+                }
+
+                if let (Some(h), Some((path, rules))) = (hunk.take(), file) {
+                    h.parse(path, rules, &mut comments);
+                }
+            }
+
+            if let Some(path) = line.strip_prefix("--- ") {
+                old_path = strip_path(path);
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                let mut path = strip_path(path);
+                if path == "/dev/null" {
+                    path = old_path;
+                }
+                file = get_syntax_from_path(path).ok().map(|rules| (path, rules));
+            } else if line.starts_with("@@ ") {
+                hunk = Hunk::from_header(line);
+            }
+        }
+
+        if let (Some(h), Some((path, rules))) = (hunk, file) {
+            h.parse(path, rules, &mut comments);
+        }
+
+        Self {
+            comments: comments.into_iter(),
+        }
+    }
+}
+
+/// Returns the path of a `---` or `+++` header, without a trailing
+/// timestamp and a leading `a/` or `b/`.
+fn strip_path(path: &str) -> &str {
+    let path = path.split('\t').next().unwrap_or(path).trim_end();
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+}
+
+/// The code of one side of a hunk, along with the kind and line number
+/// of each of its lines, and their index in the diff.
+#[derive(Default)]
+struct Side {
+    code: String,
+    lines: Vec<(DiffLine, usize, usize)>,
+    next_line: usize,
+}
+
+impl Side {
+    fn push(&mut self, line: &str, kind: DiffLine, index: usize) {
+        self.code.push_str(line);
+        self.code.push('\n');
+        self.lines.push((kind, self.next_line, index));
+        self.next_line += 1;
+    }
+}
+
+struct Hunk {
+    old: Side,
+    new: Side,
+    old_remaining: usize,
+    new_remaining: usize,
+    index: usize,
+}
+
+impl Hunk {
+    /// Parses a `@@ -l,s +l,s @@` header, where the sizes default to 1.
+    fn from_header(header: &str) -> Option<Self> {
+        let mut ranges = header[3..].split(' ');
+        let (old_start, old_len) = parse_range(ranges.next()?.strip_prefix('-')?)?;
+        let (new_start, new_len) = parse_range(ranges.next()?.strip_prefix('+')?)?;
+
+        Some(Self {
+            old: Side {
+                next_line: old_start,
+                ..Side::default()
+            },
+            new: Side {
+                next_line: new_start,
+                ..Side::default()
+            },
+            old_remaining: old_len,
+            new_remaining: new_len,
+            index: 0,
+        })
+    }
+
+    #[inline]
+    fn is_done(&self) -> bool {
+        self.old_remaining == 0 && self.new_remaining == 0
+    }
+
+    /// Pushes a line of the hunk, and returns `false` if `line` is
+    /// not part of the hunk.
+    fn push(&mut self, line: &str) -> bool {
+        let index = self.index;
+        self.index += 1;
+
+        if line.starts_with('\\') {
+            // `\ No newline at end of file`
+            return true;
+        }
+
+        // An empty line is an empty context line, with its space trimmed
+        let (marker, code) = match line.chars().next() {
+            Some(c) => (c, &line[c.len_utf8()..]),
+            None => (' ', ""),
+        };
+
+        match marker {
+            '+' if self.new_remaining > 0 => {
+                self.new.push(code, DiffLine::Added, index);
+                self.new_remaining -= 1;
+            }
+            '-' if self.old_remaining > 0 => {
+                self.old.push(code, DiffLine::Removed, index);
+                self.old_remaining -= 1;
+            }
+            ' ' if self.old_remaining > 0 && self.new_remaining > 0 => {
+                self.old.push(code, DiffLine::Context, index);
+                self.new.push(code, DiffLine::Context, index);
+                self.old_remaining -= 1;
+                self.new_remaining -= 1;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Parses both sides of the hunk, and appends the comments to
+    /// `comments` in the order of the diff.
+    fn parse<'a>(self, path: &'a str, rules: &[SyntaxRule], comments: &mut Vec<DiffComment<'a>>) {
+        let mut found = Vec::new();
+
+        for (side, keep) in [(&self.new, true), (&self.old, false)] {
+            for event in CommentParser::new(&side.code, rules) {
+                let start = range_in(&side.code, event.raw()).start;
+                let (line_kind, line, index) = side.lines[side.code[..start].matches('\n').count()];
+
+                if keep || line_kind == DiffLine::Removed {
+                    found.push((
+                        index,
+                        DiffComment {
+                            path,
+                            line_kind,
+                            line,
+                            event: OwnedEvent::from_event(&event, &side.code),
+                        },
+                    ));
+                }
+            }
+        }
+
+        found.sort_by_key(|&(index, _)| index);
+        comments.extend(found.into_iter().map(|(_, comment)| comment));
+    }
+}
+
+/// Parses the `l,s` or `l` of a hunk header.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+impl<'a> Iterator for DiffCommentParser<'a> {
+    type Item = DiffComment<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.comments.next()
+    }
+}
+
+impl<'a> FusedIterator for DiffCommentParser<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DiffCommentParser, DiffLine, EventKind};
+
+    #[test]
+    fn added_function() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 0000000..1111111 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,8 @@
+ // Foo
+ fn foo() {}
+-// Bar
++
++/// Baz
++fn baz() {
++    let s = \"// Not a comment\"; /* Qux */
++}
+ fn main() {}
+@@ -10,2 +14,2 @@ fn main() {}
+-/* Quux
++   Quux */
+ x
+diff --git a/README b/README
+--- a/README
++++ b/README
+@@ -1 +1 @@
+-// Foo
++// Bar
+";
+
+        let comments = DiffCommentParser::new(diff)
+            .map(|comment| {
+                assert_eq!(comment.path, "src/lib.rs");
+                (
+                    comment.line_kind,
+                    comment.line,
+                    comment.event.kind,
+                    comment.event.text,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            [
+                (
+                    DiffLine::Context,
+                    1,
+                    EventKind::LineComment,
+                    " Foo".to_string()
+                ),
+                (
+                    DiffLine::Removed,
+                    3,
+                    EventKind::LineComment,
+                    " Bar".to_string()
+                ),
+                (
+                    DiffLine::Added,
+                    4,
+                    EventKind::LineComment,
+                    " Baz".to_string()
+                ),
+                (
+                    DiffLine::Added,
+                    6,
+                    EventKind::BlockComment,
+                    " Qux ".to_string()
+                ),
+                (
+                    DiffLine::Removed,
+                    10,
+                    EventKind::BlockComment,
+                    " Quux\nx\n".to_string()
+                ),
+            ]
+        );
+    }
+}
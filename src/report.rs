@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::parse::{range_in, CommentParser, EventKind};
+use crate::syntax::SyntaxRule;
+
+/// Parses `source` based on `rules`, and writes a report of the
+/// comments to `out`, i.e. one line per comment in source order.
+///
+/// Each line is formatted as `line:column kind text`, where:
+///
+/// - `line` and `column` are the 1-based position of the start of
+///   the comment delimiter, where the column is counted in characters.
+/// - `kind` is either `line` or `block`.
+/// - `text` is the [`text`] of the comment, quoted and escaped like
+///   Rust's [`Debug`] for `str`, such that it fits on a single line.
+///
+/// [`text`]: enum.Event.html#method.text
+/// [`Debug`]: https://doc.rust-lang.org/std/fmt/trait.Debug.html
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, report_comments};
+/// let code = "// Foo\nfn foo() {} /* Bar\nBaz */\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let mut out = Vec::new();
+/// report_comments(code, rules, &mut out).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "1:1 line \" Foo\"\n2:13 block \" Bar\\nBaz \"\n"
+/// );
+/// ```
+pub fn report_comments<W: Write>(source: &str, rules: &[SyntaxRule], mut out: W) -> io::Result<()> {
+    for (line, column, event) in CommentParser::new(source, rules).positions(source) {
+        writeln!(
+            out,
+            "{}:{} {} {:?}",
+            line,
+            column,
+            kind_name(event.kind()),
+            event.text()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Parses `source` based on `rules`, and writes the comments to `out`
+/// as CSV, i.e. a header row followed by one row per comment in source
+/// order, with the columns `start_line,end_line,kind,text`.
+///
+/// The lines are the 1-based lines of the start of the comment
+/// delimiter and of the end of the comment. The `kind` is formatted
+/// like in [`report_comments`]. A `text` containing a comma, a quote
+/// or a line break is quoted with `"`, where quotes are doubled. Rows
+/// end with `\n`.
+///
+/// [`report_comments`]: fn.report_comments.html
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{comments_to_csv, get_syntax};
+/// let code = "// Foo\nfn foo() {} /* Bar,\n\"Baz\" */\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let mut out = Vec::new();
+/// comments_to_csv(code, rules, &mut out).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "start_line,end_line,kind,text\n1,1,line, Foo\n2,3,block,\" Bar,\n\"\"Baz\"\" \"\n"
+/// );
+/// ```
+pub fn comments_to_csv<W: Write>(source: &str, rules: &[SyntaxRule], mut out: W) -> io::Result<()> {
+    writeln!(out, "start_line,end_line,kind,text")?;
+
+    for (line, _, event) in CommentParser::new(source, rules).positions(source) {
+        let end_line = line + event.raw().matches('\n').count();
+
+        write!(out, "{},{},{},", line, end_line, kind_name(event.kind()))?;
+
+        let text = event.text();
+        if text.contains([',', '"', '\n', '\r']) {
+            writeln!(out, "\"{}\"", text.replace('"', "\"\""))?;
+        } else {
+            writeln!(out, "{}", text)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses `source` based on `rules`, and returns the number of
+/// comments using each distinct opening delimiter, i.e. the
+/// [`open_delim`] of the rule that matched, e.g. `//` and `/*`.
+///
+/// This is useful to audit style, e.g. whether a file mixes `//` and
+/// `/* */` comments. Note that the delimiter is the one of the rule,
+/// such that e.g. `/** */` is counted as `/*`.
+///
+/// [`open_delim`]: enum.Event.html#method.open_delim
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, marker_histogram};
+/// let code = "# Foo\nx = 1  # Bar\n";
+/// let rules = get_syntax("python").unwrap();
+///
+/// let histogram = marker_histogram(code, rules);
+/// assert_eq!(histogram.len(), 1);
+/// assert_eq!(histogram["#"], 2);
+/// ```
+pub fn marker_histogram(source: &str, rules: &[SyntaxRule]) -> HashMap<String, usize> {
+    let mut histogram = HashMap::new();
+
+    for event in CommentParser::new(source, rules) {
+        *histogram.entry(event.open_delim().to_string()).or_insert(0) += 1;
+    }
+
+    histogram
+}
+
+/// How comments are weighed by [`dominant_comment_style`].
+///
+/// [`dominant_comment_style`]: fn.dominant_comment_style.html
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum StyleMetric {
+    /// Each comment counts as 1.
+    Count,
+    /// Each comment counts as its length in bytes, from the start of
+    /// its [`open_delim`] to its end.
+    ///
+    /// [`open_delim`]: enum.Event.html#method.open_delim
+    Bytes,
+}
+
+/// Parses `source` based on `rules`, and returns whether it mainly
+/// uses line comments or block comments, i.e. [`EventKind::LineComment`]
+/// or [`EventKind::BlockComment`], where each comment is weighed by
+/// `metric`. Shebangs are not counted.
+///
+/// A tie is resolved in favor of line comments. Returns `None` if
+/// `source` contains no line or block comments.
+///
+/// [`EventKind::LineComment`]: enum.EventKind.html#variant.LineComment
+/// [`EventKind::BlockComment`]: enum.EventKind.html#variant.BlockComment
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{dominant_comment_style, get_syntax, EventKind, StyleMetric};
+/// let code = "// Foo\n// Bar\n/* Baz, with a long text */\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// assert_eq!(
+///     dominant_comment_style(code, rules, StyleMetric::Count),
+///     Some(EventKind::LineComment)
+/// );
+/// assert_eq!(
+///     dominant_comment_style(code, rules, StyleMetric::Bytes),
+///     Some(EventKind::BlockComment)
+/// );
+/// ```
+pub fn dominant_comment_style(
+    source: &str,
+    rules: &[SyntaxRule],
+    metric: StyleMetric,
+) -> Option<EventKind> {
+    let (mut line, mut block) = (0, 0);
+
+    for event in CommentParser::new(source, rules) {
+        let weight = match metric {
+            StyleMetric::Count => 1,
+            StyleMetric::Bytes => {
+                range_in(source, event.raw()).end - range_in(source, event.open_delim()).start
+            }
+        };
+
+        match event.kind() {
+            EventKind::LineComment => line += weight,
+            EventKind::BlockComment => block += weight,
+            EventKind::Shebang | EventKind::String => {}
+        }
+    }
+
+    if line == 0 && block == 0 {
+        None
+    } else if line >= block {
+        Some(EventKind::LineComment)
+    } else {
+        Some(EventKind::BlockComment)
+    }
+}
+
+#[inline]
+fn kind_name(kind: EventKind) -> &'static str {
+    match kind {
+        EventKind::LineComment => "line",
+        EventKind::BlockComment => "block",
+        EventKind::Shebang => "shebang",
+        EventKind::String => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        comments_to_csv, dominant_comment_style, get_syntax, marker_histogram, report_comments,
+        EventKind, StyleMetric,
+    };
+
+    #[test]
+    fn report() {
+        let code = "
+# Foo
+def foo():
+    \"\"\"Not a comment\"\"\"
+    pass  # Bar
+";
+        let rules = get_syntax("python").unwrap();
+
+        let mut out = Vec::new();
+        report_comments(code, rules, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2:1 line \" Foo\"\n5:11 line \" Bar\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_quoting() {
+        let code = "# Foo, bar\necho \"# Baz\" # Qux\n";
+        let rules = get_syntax("shell").unwrap();
+
+        let mut out = Vec::new();
+        comments_to_csv(code, rules, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "start_line,end_line,kind,text\n1,1,line,\" Foo, bar\"\n2,2,line, Qux\n"
+        );
+
+        let code = "/* \"Foo\"\r\n   Bar */ // Baz\n";
+        let rules = get_syntax("c").unwrap();
+
+        let mut out = Vec::new();
+        comments_to_csv(code, rules, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "start_line,end_line,kind,text\n1,2,block,\" \"\"Foo\"\"\r\n   Bar \"\n2,2,line, Baz\n"
+        );
+    }
+
+    #[test]
+    fn markers() {
+        let code = r#"
+//! Foo
+//! Bar
+
+/// Baz
+/** Qux */
+fn main() {
+    // Quux
+    let s = "// Not a comment"; /* Quuz */
+    // Corge
+}
+"#;
+        let rules = get_syntax("rust").unwrap();
+
+        let histogram = marker_histogram(code, rules);
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram["//!"], 2);
+        assert_eq!(histogram["///"], 1);
+        assert_eq!(histogram["//"], 2);
+        assert_eq!(histogram["/*"], 2);
+
+        assert!(marker_histogram("fn main() {}", rules).is_empty());
+    }
+
+    #[test]
+    fn dominant_line_comments() {
+        let code = "#!/usr/bin/env foo\n// Foo\nfn main() {} // Bar\n/* Baz */\n";
+        let rules = get_syntax("rust").unwrap();
+
+        for &metric in &[StyleMetric::Count, StyleMetric::Bytes] {
+            assert_eq!(
+                dominant_comment_style(code, rules, metric),
+                Some(EventKind::LineComment)
+            );
+        }
+    }
+
+    #[test]
+    fn dominant_block_comments() {
+        let code = "/**\n * Foo\n */\nint main() { /* Bar */ } // Baz\n";
+        let rules = get_syntax("c").unwrap();
+
+        for &metric in &[StyleMetric::Count, StyleMetric::Bytes] {
+            assert_eq!(
+                dominant_comment_style(code, rules, metric),
+                Some(EventKind::BlockComment)
+            );
+        }
+    }
+
+    #[test]
+    fn dominant_tie() {
+        // Both comments are 7 bytes, so it is a tie by both metrics
+        let code = "// Foo!\n/* B */\n";
+        let rules = get_syntax("rust").unwrap();
+
+        for &metric in &[StyleMetric::Count, StyleMetric::Bytes] {
+            assert_eq!(
+                dominant_comment_style(code, rules, metric),
+                Some(EventKind::LineComment)
+            );
+        }
+
+        let code = "fn main() {}\n";
+        assert_eq!(
+            dominant_comment_style(code, rules, StyleMetric::Count),
+            None
+        );
+    }
+}
@@ -0,0 +1,999 @@
+use std::borrow::Cow;
+use std::ops::{Range, RangeInclusive};
+
+use line_span::find_line_start;
+
+use crate::incremental::IncrementalCommentParser;
+use crate::parse::{range_in, Category, CommentParser, Event, EventKind};
+use crate::syntax::SyntaxRule;
+
+/// `Source` is text which is not necessarily stored contiguously,
+/// such as a rope or another segmented buffer used by editors.
+///
+/// Use [`parse_source`] to parse the comments of a `Source`.
+///
+/// [`parse_source`]: fn.parse_source.html
+///
+/// # Example
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use std::ops::Range;
+/// use comment_parser::Source;
+///
+/// struct Chunks(Vec<String>);
+///
+/// impl Source for Chunks {
+///     fn len(&self) -> usize {
+///         self.0.iter().map(String::len).sum()
+///     }
+///
+///     fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+///         let text = self.0.concat();
+///         Cow::Owned(text[range].to_string())
+///     }
+///
+///     fn chunk(&self, mut start: usize) -> Cow<'_, str> {
+///         for chunk in &self.0 {
+///             if start < chunk.len() {
+///                 return Cow::Borrowed(&chunk[start..]);
+///             }
+///             start -= chunk.len();
+///         }
+///         Cow::Borrowed("")
+///     }
+/// }
+/// ```
+pub trait Source {
+    /// Returns the length of the text in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the text is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the text in the byte `range`.
+    ///
+    /// The `range` is always within bounds and on `char` boundaries.
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str>;
+
+    /// Returns the contiguous text starting at byte index `start`, up
+    /// to the end of the segment containing it, which is not empty if
+    /// `start` is less than [`len`].
+    ///
+    /// The default implementation slices the rest of the text. A
+    /// segmented buffer, such as a rope, should return its segments
+    /// instead, such that [`parse_source`] does not copy the text.
+    ///
+    /// [`len`]: trait.Source.html#tymethod.len
+    /// [`parse_source`]: fn.parse_source.html
+    #[inline]
+    fn chunk(&self, start: usize) -> Cow<'_, str> {
+        self.slice(start..self.len())
+    }
+}
+
+impl Source for str {
+    #[inline]
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+}
+
+impl Source for String {
+    #[inline]
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+}
+
+/// Parses `source` based on `rules`, and returns each comment as
+/// `(kind, raw_range, text_range)`, where the ranges are byte ranges
+/// in `source`, as returned by [`Event::spans`].
+///
+/// A `source` that is not contiguous, e.g. a rope, is parsed chunk by
+/// chunk, as returned by [`Source::chunk`], using an
+/// [`IncrementalCommentParser`]. Thereby, it is never copied as a whole,
+/// and only the current line and any block comment or string spanning
+/// chunks is buffered. Contiguous sources, such as `str`, are parsed
+/// without copying.
+///
+/// [`Source::chunk`]: trait.Source.html#method.chunk
+/// [`IncrementalCommentParser`]: struct.IncrementalCommentParser.html
+/// [`Event::spans`]: enum.Event.html#method.spans
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, parse_source, EventKind};
+/// let code = "foo /* Foo */ bar";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// assert_eq!(
+///     parse_source(code, rules),
+///     [(EventKind::BlockComment, 4..13, 6..11)]
+/// );
+/// ```
+pub fn parse_source<S>(
+    source: &S,
+    rules: &[SyntaxRule],
+) -> Vec<(EventKind, Range<usize>, Range<usize>)>
+where
+    S: Source + ?Sized,
+{
+    // A contiguous source is parsed directly
+    let first = source.chunk(0);
+    if first.len() == source.len() {
+        let text = first.as_ref();
+        return CommentParser::new(text, rules)
+            .map(|event| {
+                let (raw, text) = event.spans(text);
+                (event.kind(), raw, text)
+            })
+            .collect();
+    }
+
+    let mut parser = IncrementalCommentParser::new(rules);
+    let mut events = Vec::new();
+    let mut index = 0;
+
+    while index < source.len() {
+        let chunk = source.chunk(index);
+        assert!(!chunk.is_empty(), "Source::chunk returned an empty chunk");
+
+        parser.feed(&chunk);
+        index += chunk.len();
+        events.extend(parser.take_events());
+    }
+    events.extend(parser.finish());
+
+    events
+        .into_iter()
+        .map(|event| (event.kind, event.raw_range, event.text_range))
+        .collect()
+}
+
+/// `OwnedEvent` is an owned comment, returned by [`comments_in_range`].
+///
+/// [`comments_in_range`]: fn.comments_in_range.html
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct OwnedEvent {
+    /// The kind of the comment.
+    pub kind: EventKind,
+    /// The [`raw`] text of the comment.
+    ///
+    /// [`raw`]: enum.Event.html#method.raw
+    pub raw: String,
+    /// The [`text`] of the comment.
+    ///
+    /// [`text`]: enum.Event.html#method.text
+    pub text: String,
+    /// The byte range of [`raw`] in the source.
+    ///
+    /// [`raw`]: struct.OwnedEvent.html#structfield.raw
+    pub raw_range: Range<usize>,
+    /// The byte range of [`text`] in the source.
+    ///
+    /// [`text`]: struct.OwnedEvent.html#structfield.text
+    pub text_range: Range<usize>,
+}
+
+impl OwnedEvent {
+    /// Creates an `OwnedEvent` from `event`, where the ranges are
+    /// byte ranges in `source`.
+    pub(crate) fn from_event(event: &Event, source: &str) -> Self {
+        let (raw_range, text_range) = event.spans(source);
+        Self {
+            kind: event.kind(),
+            raw: event.raw().to_string(),
+            text: event.text().to_string(),
+            raw_range,
+            text_range,
+        }
+    }
+}
+
+impl<'a> CommentParser<'a> {
+    /// Consumes the `CommentParser` and returns an iterator over the
+    /// comments as [`OwnedEvent`]s, where the ranges are byte ranges
+    /// in `source`, plus the [base offset].
+    ///
+    /// If [`strip_cr`] is enabled, then the `\r` of `\r\n` sequences
+    /// is excluded from the text of block comments. The ranges are
+    /// those of the comment in `source` regardless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not contain the text given to [`new`].
+    ///
+    /// [`OwnedEvent`]: struct.OwnedEvent.html
+    /// [base offset]: struct.CommentParser.html#method.with_base_offset
+    /// [`strip_cr`]: struct.CommentParser.html#method.strip_cr
+    /// [`new`]: struct.CommentParser.html#method.new
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{get_syntax, CommentParser};
+    /// let code = "int x; // Foo";
+    /// let rules = get_syntax("c").unwrap();
+    ///
+    /// let events = CommentParser::new(code, rules).owned_events(code).collect::<Vec<_>>();
+    /// assert_eq!(events[0].text, " Foo");
+    /// assert_eq!(events[0].raw_range, 0..13);
+    /// ```
+    pub fn owned_events(self, source: &'a str) -> impl Iterator<Item = OwnedEvent> + 'a {
+        let strip_cr = self.strips_cr();
+        let base = self.base_offset();
+        self.map(move |event| {
+            let mut owned = OwnedEvent::from_event(&event, source);
+            owned.raw_range = base + owned.raw_range.start..base + owned.raw_range.end;
+            owned.text_range = base + owned.text_range.start..base + owned.text_range.end;
+            if strip_cr && owned.kind == EventKind::BlockComment {
+                owned.text = owned.text.replace("\r\n", "\n");
+            }
+            owned
+        })
+    }
+}
+
+/// Parses the byte `range` of `source` based on `rules`, and returns
+/// each comment starting within `range` as an [`OwnedEvent`], where
+/// the ranges are byte ranges in `source`.
+///
+/// Parsing begins at the start of `range`, like [`new_from`], and stops
+/// at the first comment starting at or after the end of `range`. A
+/// comment starting within `range` is returned whole, even if it ends
+/// after it. A comment starting before `range` is not returned, and its
+/// remainder is parsed as code, so `range` should not start within one.
+///
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+/// [`new_from`]: struct.CommentParser.html#method.new_from
+///
+/// # Panics
+///
+/// Panics if `range` is out of bounds or not on `char` boundaries.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{comments_in_range, get_syntax};
+/// let code = "// Foo\n/* Bar */\n// Baz\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let comments = comments_in_range(code, rules, 7..16);
+///
+/// assert_eq!(comments.len(), 1);
+/// assert_eq!(comments[0].text, " Bar ");
+/// assert_eq!(comments[0].raw_range, 7..16);
+/// ```
+pub fn comments_in_range(
+    source: &str,
+    rules: &[SyntaxRule],
+    range: Range<usize>,
+) -> Vec<OwnedEvent> {
+    assert!(
+        range.start <= range.end && source.is_char_boundary(range.end),
+        "range is out of bounds or not on char boundaries"
+    );
+
+    let mut parser = CommentParser::new_from(source, rules, range.start);
+    let mut comments = Vec::new();
+
+    while let Some((start, event)) = parser.next_with_start() {
+        if start >= range.end {
+            break;
+        }
+
+        comments.push(OwnedEvent::from_event(&event, source));
+    }
+
+    comments
+}
+
+/// Returns the license header of `source`, i.e. the [leading comment
+/// block], if it contains `Copyright`, `License` or `SPDX-License-Identifier`,
+/// regardless of casing. Otherwise, returns `None`.
+///
+/// [leading comment block]: struct.CommentParser.html#method.leading_block
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{extract_license_header, get_syntax};
+/// let code = "// SPDX-License-Identifier: MIT\nfn main() {}\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let header = extract_license_header(code, rules).unwrap();
+/// assert_eq!(header.text, " SPDX-License-Identifier: MIT");
+/// assert_eq!(header.raw_range, 0..31);
+/// ```
+pub fn extract_license_header(source: &str, rules: &[SyntaxRule]) -> Option<OwnedEvent> {
+    let block = CommentParser::new(source, rules).leading_block(source)?;
+
+    let text = block.text().to_lowercase();
+    if text.contains("copyright") || text.contains("license") {
+        Some(OwnedEvent::from_event(&block, source))
+    } else {
+        None
+    }
+}
+
+/// Returns the copyright years in the comments of `source`, e.g.
+/// `2020..=2020` for `Copyright (c) 2020` and `2019..=2021` for
+/// `Copyright 2019-2021`, in order of appearance.
+///
+/// The years follow `Copyright`, regardless of casing, and an optional
+/// `(c)` or `©`. Multiple years are separated by commas, e.g.
+/// `Copyright 2018, 2020-2021`. A range with a 2-digit end, e.g.
+/// `2019-21`, ends in the century of its start, and a reversed range
+/// is swapped.
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{copyright_years, get_syntax};
+/// let code = "// Copyright (c) 2019-2021 Foo\nfn main() {} // Copyright 2023\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// assert_eq!(copyright_years(code, rules), [2019..=2021, 2023..=2023]);
+/// ```
+pub fn copyright_years(source: &str, rules: &[SyntaxRule]) -> Vec<RangeInclusive<u16>> {
+    let mut years = Vec::new();
+
+    for event in CommentParser::new(source, rules) {
+        let text = event.text();
+        let lower = text.to_ascii_lowercase();
+
+        for (i, _) in lower.match_indices("copyright") {
+            parse_years(&text[i + "copyright".len()..], &mut years);
+        }
+    }
+
+    years
+}
+
+/// Parses the years at the start of `text`, i.e. after `Copyright`,
+/// and appends them to `years`.
+fn parse_years(text: &str, years: &mut Vec<RangeInclusive<u16>>) {
+    let mut text = text.trim_start();
+    for symbol in &["(c)", "(C)", "©"] {
+        if let Some(rest) = text.strip_prefix(symbol) {
+            text = rest.trim_start();
+            break;
+        }
+    }
+
+    loop {
+        let (start, rest) = match parse_number(text) {
+            Some((start, rest)) if start.len() == 4 => (start, rest),
+            _ => return,
+        };
+        let start = start.parse::<u16>().unwrap();
+        text = rest.trim_start();
+
+        let mut end = start;
+        if let Some(rest) = text.strip_prefix(['-', '–']) {
+            match parse_number(rest.trim_start()) {
+                Some((year, rest)) if year.len() == 4 => {
+                    end = year.parse().unwrap();
+                    text = rest.trim_start();
+                }
+                Some((year, rest)) if year.len() == 2 => {
+                    end = start / 100 * 100 + year.parse::<u16>().unwrap();
+                    text = rest.trim_start();
+                }
+                _ => {}
+            }
+        }
+
+        years.push(start.min(end)..=start.max(end));
+
+        match text.strip_prefix(',') {
+            Some(rest) => text = rest.trim_start(),
+            None => return,
+        }
+    }
+}
+
+/// Returns the ASCII digits at the start of `text`, along with the
+/// rest of `text`, if there are any.
+#[inline]
+fn parse_number(text: &str) -> Option<(&str, &str)> {
+    let len = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if len == 0 || text[len..].starts_with(|c: char| c.is_alphanumeric()) {
+        None
+    } else {
+        Some(text.split_at(len))
+    }
+}
+
+/// The keywords used by [`doc_for_identifier`] to find declarations.
+///
+/// [`doc_for_identifier`]: fn.doc_for_identifier.html
+const DECLARATION_KEYWORDS: [&str; 3] = ["fn", "def", "class"];
+
+/// Returns the doc comment of the declaration of `name` in `source`,
+/// i.e. the first `fn name`, `def name` or `class name` outside of
+/// comments and strings. Returns `None` if there is no declaration,
+/// or it is not immediately preceded by a doc comment.
+///
+/// *See [`doc_for_identifier_with`] for custom keywords.*
+///
+/// [`doc_for_identifier_with`]: fn.doc_for_identifier_with.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{doc_for_identifier, get_syntax};
+/// let code = "/// Foo\nfn foo() {}\n\n// Bar\nfn bar() {}\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let doc = doc_for_identifier(code, rules, "foo").unwrap();
+/// assert_eq!(doc.text, " Foo");
+///
+/// assert_eq!(doc_for_identifier(code, rules, "bar"), None);
+/// ```
+#[inline]
+pub fn doc_for_identifier(source: &str, rules: &[SyntaxRule], name: &str) -> Option<OwnedEvent> {
+    doc_for_identifier_with(source, rules, name, &DECLARATION_KEYWORDS)
+}
+
+/// Returns the doc comment of the declaration of `name` in `source`,
+/// where a declaration is one of `keywords` followed by `name`, e.g.
+/// `struct name` for the keyword `struct`.
+///
+/// Consecutive doc comments, separated by at most a single newline,
+/// are merged like a [leading comment block]. Attribute and decorator
+/// lines between the doc comment and the declaration, i.e. lines
+/// starting with `#[` or `@`, are skipped.
+///
+/// *See [`Event::is_doc`] for which comments are doc comments.* If no
+/// rule starts with `//` or `/*`, e.g. for Python, then any comment
+/// that is not a directive counts as a doc comment.
+///
+/// [leading comment block]: struct.CommentParser.html#method.leading_block
+/// [`Event::is_doc`]: enum.Event.html#method.is_doc
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{doc_for_identifier_with, get_syntax};
+/// let code = "/// Foo\n/// Bar\n#[derive(Debug)]\npub struct Foo;\n";
+/// let rules = get_syntax("rust").unwrap();
+///
+/// let doc = doc_for_identifier_with(code, rules, "Foo", &["struct"]).unwrap();
+/// assert_eq!(doc.raw, "/// Foo\n/// Bar");
+/// assert_eq!(doc.raw_range, 0..15);
+/// ```
+pub fn doc_for_identifier_with(
+    source: &str,
+    rules: &[SyntaxRule],
+    name: &str,
+    keywords: &[&str],
+) -> Option<OwnedEvent> {
+    let mut parser = CommentParser::new(source, rules).include_strings(true);
+    let mut ranges = Vec::new();
+    let mut comments = Vec::new();
+
+    while let Some((start, event)) = parser.next_with_start() {
+        let range = start..range_in(source, event.raw()).end;
+        ranges.push(range.clone());
+        if !event.is_string() {
+            comments.push((range, event));
+        }
+    }
+
+    let declaration = keywords
+        .iter()
+        .filter_map(|keyword| find_declaration(source, keyword, name, &ranges))
+        .min()?;
+
+    let mut end = find_line_start(source, declaration);
+    while end > 0 {
+        let start = find_line_start(source, end - 1);
+        let line = source[start..end].trim();
+        if !line.starts_with("#[") && !line.starts_with('@') {
+            break;
+        }
+        end = start;
+    }
+
+    let has_doc_syntax = rules
+        .iter()
+        .any(|rule| matches!(rule.inner().start(), b"//" | b"/*"));
+    let is_doc = |event: &Event| {
+        event.is_doc() || (!has_doc_syntax && event.category() == Category::Ordinary)
+    };
+
+    let mut block: Option<(&Event, &Event)> = None;
+
+    for (range, event) in comments.iter().rev() {
+        if range.end > end {
+            continue;
+        }
+
+        let gap = &source[range.end..end];
+        if !gap.trim().is_empty() || gap.matches('\n').count() > 1 || !is_doc(event) {
+            break;
+        }
+
+        block = Some((event, block.map_or(event, |(_, last)| last)));
+        end = range.start;
+    }
+
+    let (first, last) = block?;
+
+    let raw = range_in(source, first.raw()).start..range_in(source, last.raw()).end;
+    let text = range_in(source, first.text()).start..range_in(source, last.text()).end;
+
+    Some(OwnedEvent::from_event(
+        &first.with_parts(&source[raw], &source[text]),
+        source,
+    ))
+}
+
+/// Returns the byte index of the first `keyword` in `source`, which is
+/// followed by `name` and not inside any of `ranges`.
+fn find_declaration(
+    source: &str,
+    keyword: &str,
+    name: &str,
+    ranges: &[Range<usize>],
+) -> Option<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    source.match_indices(keyword).map(|(i, _)| i).find(|&i| {
+        if source[..i].chars().next_back().is_some_and(is_ident) {
+            return false;
+        }
+
+        let rest = &source[i + keyword.len()..];
+        let after = rest.trim_start_matches([' ', '\t']);
+
+        after.len() < rest.len()
+            && after.starts_with(name)
+            && !after[name.len()..].starts_with(is_ident)
+            && !ranges.iter().any(|range| range.contains(&i))
+    })
+}
+
+/// The encoding of the bytes given to [`comments_from_encoded`].
+///
+/// [`comments_from_encoded`]: fn.comments_from_encoded.html
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Encoding {
+    /// UTF-8.
+    Utf8,
+    /// UTF-16 little-endian, common for source files on Windows.
+    Utf16Le,
+    /// UTF-16 big-endian.
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Decodes `bytes` into UTF-8, excluding a leading byte order mark.
+    /// Invalid sequences, including a trailing odd byte in UTF-16,
+    /// are replaced with `U+FFFD`.
+    fn decode(self, bytes: &[u8]) -> String {
+        let utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+            let units = bytes
+                .chunks(2)
+                .map(|unit| match *unit {
+                    [a, b] => from_bytes([a, b]),
+                    _ => 0xFFFD,
+                })
+                .collect::<Vec<_>>();
+            let units = units.strip_prefix(&[0xFEFF]).unwrap_or(&units);
+            char::decode_utf16(units.iter().copied())
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect()
+        };
+
+        match self {
+            Encoding::Utf8 => {
+                let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+            Encoding::Utf16Le => utf16(bytes, u16::from_le_bytes),
+            Encoding::Utf16Be => utf16(bytes, u16::from_be_bytes),
+        }
+    }
+}
+
+/// Decodes `bytes` from `encoding` into UTF-8, and then parses the
+/// decoded text based on `rules`, returning each comment as an
+/// [`OwnedEvent`].
+///
+/// The ranges are byte ranges in the decoded UTF-8 text, not in
+/// `bytes`. A leading byte order mark is not part of the decoded text,
+/// and invalid sequences are replaced with `U+FFFD`.
+///
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{comments_from_encoded, get_syntax, Encoding};
+/// let bytes = "x = 1 # Foo"
+///     .encode_utf16()
+///     .flat_map(u16::to_le_bytes)
+///     .collect::<Vec<_>>();
+/// let rules = get_syntax("python").unwrap();
+///
+/// let comments = comments_from_encoded(&bytes, Encoding::Utf16Le, rules);
+///
+/// assert_eq!(comments[0].text, " Foo");
+/// assert_eq!(comments[0].text_range, 7..11);
+/// ```
+pub fn comments_from_encoded(
+    bytes: &[u8],
+    encoding: Encoding,
+    rules: &[SyntaxRule],
+) -> Vec<OwnedEvent> {
+    let text = encoding.decode(bytes);
+    comments_in_range(&text, rules, 0..text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::ops::Range;
+
+    use crate::{
+        comments_from_encoded, comments_in_range, copyright_years, doc_for_identifier,
+        doc_for_identifier_with, extract_license_header, get_syntax, parse_source, CommentParser,
+        Encoding, EventKind, Source,
+    };
+
+    struct TwoChunks(&'static str, &'static str);
+
+    impl Source for TwoChunks {
+        fn len(&self) -> usize {
+            self.0.len() + self.1.len()
+        }
+
+        fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+            let mid = self.0.len();
+            if range.end <= mid {
+                Cow::Borrowed(&self.0[range])
+            } else if range.start >= mid {
+                Cow::Borrowed(&self.1[range.start - mid..range.end - mid])
+            } else {
+                let mut text = self.0[range.start..].to_string();
+                text.push_str(&self.1[..range.end - mid]);
+                Cow::Owned(text)
+            }
+        }
+
+        fn chunk(&self, start: usize) -> Cow<'_, str> {
+            let mid = self.0.len();
+            if start < mid {
+                Cow::Borrowed(&self.0[start..])
+            } else {
+                Cow::Borrowed(&self.1[start - mid..])
+            }
+        }
+    }
+
+    /// A source which can only be accessed chunk by chunk.
+    struct Chunks(&'static [&'static str]);
+
+    impl Source for Chunks {
+        fn len(&self) -> usize {
+            self.0.iter().map(|chunk| chunk.len()).sum()
+        }
+
+        fn slice(&self, _range: Range<usize>) -> Cow<'_, str> {
+            unreachable!("the source is copied")
+        }
+
+        fn chunk(&self, mut start: usize) -> Cow<'_, str> {
+            for chunk in self.0 {
+                if start < chunk.len() {
+                    return Cow::Borrowed(&chunk[start..]);
+                }
+                start -= chunk.len();
+            }
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn chunk_by_chunk() {
+        let source = Chunks(&[
+            "// Foo
+let s = \"/",
+            "* Bar */\"; /",
+            "* Baz",
+            " */ // Qux",
+        ]);
+        let rules = get_syntax("rust").unwrap();
+
+        assert_eq!(
+            parse_source(&source, rules),
+            [
+                (EventKind::LineComment, 0..6, 2..6),
+                (EventKind::BlockComment, 28..37, 30..35),
+                (EventKind::LineComment, 7..44, 40..44),
+            ]
+        );
+    }
+
+    #[test]
+    fn two_chunks() {
+        let source = TwoChunks("let x = 1; /* Fo", "o */\n// Bar\n");
+        let rules = get_syntax("rust").unwrap();
+
+        let comments = parse_source(&source, rules);
+        assert_eq!(
+            comments,
+            [
+                (EventKind::BlockComment, 11..20, 13..18),
+                (EventKind::LineComment, 21..27, 23..27),
+            ]
+        );
+
+        let (_, _, text) = comments[0].clone();
+        assert_eq!(source.slice(text), " Foo ");
+
+        let (_, _, text) = comments[1].clone();
+        assert_eq!(source.slice(text), " Bar");
+    }
+
+    #[test]
+    fn str_source() {
+        let code = String::from("# Foo\n");
+        let rules = get_syntax("python").unwrap();
+
+        assert_eq!(
+            parse_source(&code, rules),
+            parse_source(code.as_str(), rules)
+        );
+        assert_eq!(
+            parse_source(&code, rules),
+            [(EventKind::LineComment, 0..5, 1..5)]
+        );
+    }
+
+    #[test]
+    fn range_inside() {
+        let code = "// Foo\nlet x = 1; /* Bar */ // Baz\n// Qux\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let comments = comments_in_range(code, rules, 7..34);
+        assert_eq!(
+            comments
+                .iter()
+                .map(|event| (event.kind, event.text.as_str(), event.text_range.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (EventKind::BlockComment, " Bar ", 20..25),
+                (EventKind::LineComment, " Baz", 30..34),
+            ]
+        );
+        assert_eq!(comments[1].raw, "let x = 1; /* Bar */ // Baz");
+        assert_eq!(comments[1].raw_range, 7..34);
+    }
+
+    #[test]
+    fn range_straddling() {
+        let code = "/* Foo */ bar(); /* Baz\n Qux */\n";
+        let rules = get_syntax("rust").unwrap();
+
+        let comments = comments_in_range(code, rules, 10..20);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].raw, "/* Baz\n Qux */");
+        assert_eq!(comments[0].raw_range, 17..31);
+        assert_eq!(&code[comments[0].text_range.clone()], " Baz\n Qux ");
+    }
+
+    #[test]
+    fn range_outside() {
+        let code = "// Foo\nfn foo() {}\n// Bar\n";
+        let rules = get_syntax("rust").unwrap();
+
+        assert!(comments_in_range(code, rules, 7..19).is_empty());
+        assert!(comments_in_range(code, rules, 7..7).is_empty());
+        assert_eq!(comments_in_range(code, rules, 0..code.len()).len(), 2);
+    }
+
+    #[test]
+    fn license_header_block() {
+        let code = "/*
+ * Copyright (c) 2020 Foo
+ *
+ * Permission is hereby granted, free of charge, to any person
+ */
+
+/* Bar */
+int main() {}
+";
+        let rules = get_syntax("c").unwrap();
+
+        let header = extract_license_header(code, rules).unwrap();
+        assert_eq!(header.kind, EventKind::BlockComment);
+        assert!(header.text.contains("Copyright (c) 2020 Foo"));
+        assert!(!header.text.contains("Bar"));
+        assert_eq!(&code[header.raw_range], header.raw);
+    }
+
+    #[test]
+    fn license_header_spdx() {
+        let code = "# SPDX-License-Identifier: Apache-2.0\n# Foo\nimport os\n";
+        let rules = get_syntax("python").unwrap();
+
+        let header = extract_license_header(code, rules).unwrap();
+        assert_eq!(header.raw, "# SPDX-License-Identifier: Apache-2.0\n# Foo");
+        assert_eq!(header.raw_range, 0..43);
+    }
+
+    #[test]
+    fn license_header_none() {
+        let rules = get_syntax("rust").unwrap();
+
+        let code = "// Foo\n// Bar\nfn main() {} // Copyright\n";
+        assert_eq!(extract_license_header(code, rules), None);
+
+        let code = "fn main() {}\n// License: MIT\n";
+        assert_eq!(extract_license_header(code, rules), None);
+    }
+
+    #[test]
+    fn copyright_single_year() {
+        let code = "# Copyright (C) 2020 Foo\n";
+        let rules = get_syntax("python").unwrap();
+        assert_eq!(copyright_years(code, rules), [2020..=2020]);
+
+        let code = "# Copyright Foo\n# copyright: 12345\ns = 'Copyright 2021'\n";
+        assert!(copyright_years(code, rules).is_empty());
+    }
+
+    #[test]
+    fn copyright_range() {
+        let code = "/*\n * Copyright © 2019 - 2021 Foo\n * COPYRIGHT 2018-20, 2023–2022\n */\n";
+        let rules = get_syntax("c").unwrap();
+
+        assert_eq!(
+            copyright_years(code, rules),
+            [2019..=2021, 2018..=2020, 2022..=2023]
+        );
+    }
+
+    #[test]
+    fn copyright_multiple_lines() {
+        let code = "// Copyright 2015, 2017 Foo\n// Copyright (c) 2020-2021 Bar\nfn main() {}\n";
+        let rules = get_syntax("rust").unwrap();
+
+        assert_eq!(
+            copyright_years(code, rules),
+            [2015..=2015, 2017..=2017, 2020..=2021]
+        );
+    }
+
+    #[test]
+    fn strip_cr() {
+        let code = "/* Foo\r\n * Bar\r\n */\r\n// Baz\r\n";
+        let rules = get_syntax("c").unwrap();
+
+        let texts = |strip| {
+            CommentParser::new(code, rules)
+                .strip_cr(strip)
+                .owned_events(code)
+                .map(|event| event.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts(true), [" Foo\n * Bar\n ", " Baz"]);
+        assert_eq!(texts(false), [" Foo\r\n * Bar\r\n ", " Baz"]);
+
+        let events = CommentParser::new(code, rules)
+            .strip_cr(true)
+            .owned_events(code)
+            .collect::<Vec<_>>();
+        assert_eq!(events[0].raw, "/* Foo\r\n * Bar\r\n */");
+        assert_eq!(events[0].text_range, 2..17);
+    }
+
+    #[test]
+    fn utf16_le() {
+        let code = "\u{FEFF}// Føø\r\nlet s = \"/* Bar */\"; /* Bäz */\r\n";
+        let bytes = code
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let rules = get_syntax("rust").unwrap();
+
+        let comments = comments_from_encoded(&bytes, Encoding::Utf16Le, rules);
+        assert_eq!(
+            comments
+                .iter()
+                .map(|event| (event.kind, event.text.as_str(), event.raw_range.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (EventKind::LineComment, " Føø", 0..8),
+                (EventKind::BlockComment, " Bäz ", 31..41),
+            ]
+        );
+
+        let bytes = code
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            comments_from_encoded(&bytes, Encoding::Utf16Be, rules),
+            comments
+        );
+        assert_eq!(
+            comments_from_encoded(code.as_bytes(), Encoding::Utf8, rules),
+            comments
+        );
+    }
+
+    #[test]
+    fn utf16_invalid() {
+        let mut bytes = "# Foo"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        bytes.extend_from_slice(&[0x00, 0xD8, b'x']);
+        let rules = get_syntax("python").unwrap();
+
+        let comments = comments_from_encoded(&bytes, Encoding::Utf16Le, rules);
+        assert_eq!(comments[0].text, " Foo\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn doc_for_named_function() {
+        let code = r#"
+/// Not foo
+fn bar() {}
+
+/** Foo */
+/// Adds one
+#[inline]
+fn foo(x: i32) -> i32 { x + 1 }
+
+// Plain
+fn baz() {}
+"#;
+        let rules = get_syntax("rust").unwrap();
+
+        let doc = doc_for_identifier(code, rules, "foo").unwrap();
+        assert_eq!(doc.kind, EventKind::BlockComment);
+        assert_eq!(doc.raw, "/** Foo */\n/// Adds one");
+        assert_eq!(&code[doc.raw_range], doc.raw);
+
+        assert_eq!(doc_for_identifier(code, rules, "ba"), None);
+        assert_eq!(doc_for_identifier(code, rules, "baz"), None);
+        assert_eq!(doc_for_identifier(code, rules, "qux"), None);
+    }
+
+    #[test]
+    fn doc_for_undocumented() {
+        let code = "# -*- coding: utf-8 -*-\ndef foo(): pass\n\n# Bar\n\nclass Bar: pass\n";
+        let rules = get_syntax("python").unwrap();
+
+        assert_eq!(doc_for_identifier(code, rules, "foo"), None);
+        assert_eq!(doc_for_identifier(code, rules, "Bar"), None);
+
+        let code = "s = 'def foo'\n# Foo\n@cache\ndef foo(): pass\n";
+        let doc = doc_for_identifier(code, rules, "foo").unwrap();
+        assert_eq!(doc.text, " Foo");
+
+        let rules = get_syntax("rust").unwrap();
+        assert_eq!(doc_for_identifier_with(code, rules, "foo", &["fn"]), None);
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use line_span::find_line_start;
+
+use crate::parse::{range_in, CommentParser, Event, EventKind};
+use crate::source::OwnedEvent;
+use crate::syntax::SyntaxRule;
+
+/// `ChunkStream` is an asynchronous source of bytes, read in chunks,
+/// such as a file or socket, parsed by [`AsyncCommentParser`].
+///
+/// This mirrors a `Stream` of `io::Result<Vec<u8>>`, such that any
+/// async runtime can be adapted without further dependencies.
+///
+/// [`AsyncCommentParser`]: struct.AsyncCommentParser.html
+pub trait ChunkStream {
+    /// Attempts to read the next chunk, returning `Poll::Ready(None)`
+    /// at the end of the stream.
+    ///
+    /// If no chunk is available yet, then `Poll::Pending` is returned,
+    /// and the waker of `cx` is woken once the stream can make progress.
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<Vec<u8>>>>;
+}
+
+/// `AsyncCommentParser` parses the comments of a [`ChunkStream`], and
+/// produces them as [`OwnedEvent`]s, as soon as they are complete.
+///
+/// Block comments and strings may span any number of chunks. A UTF-8
+/// sequence split across chunks is decoded once complete, while invalid
+/// UTF-8 is replaced with `U+FFFD`. The ranges of the produced
+/// [`OwnedEvent`]s are byte ranges in the decoded text of the whole
+/// stream.
+///
+/// With `futures`, an `AsyncCommentParser` can be turned into a `Stream`
+/// using `futures::stream::poll_fn(move |cx| parser.poll_next(cx))`.
+///
+/// [`ChunkStream`]: trait.ChunkStream.html
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::VecDeque;
+/// # use std::io;
+/// # use std::sync::Arc;
+/// # use std::task::{Context, Poll, Wake, Waker};
+/// use comment_parser::{get_syntax, AsyncCommentParser, ChunkStream};
+///
+/// struct Chunks(VecDeque<&'static str>);
+///
+/// impl ChunkStream for Chunks {
+///     fn poll_chunk(&mut self, _cx: &mut Context<'_>) -> Poll<Option<io::Result<Vec<u8>>>> {
+///         Poll::Ready(self.0.pop_front().map(|chunk| Ok(chunk.into())))
+///     }
+/// }
+///
+/// let stream = Chunks(vec!["int x; /* Fo", "o */"].into());
+/// let mut parser = AsyncCommentParser::new(stream, get_syntax("c").unwrap());
+///
+/// # struct Noop;
+/// # impl Wake for Noop {
+/// #     fn wake(self: Arc<Self>) {}
+/// # }
+/// # let waker = Waker::from(Arc::new(Noop));
+/// let mut cx = Context::from_waker(&waker);
+/// match parser.poll_next(&mut cx) {
+///     Poll::Ready(Some(Ok(event))) => assert_eq!(event.text, " Foo "),
+///     _ => unreachable!(),
+/// }
+/// assert!(matches!(parser.poll_next(&mut cx), Poll::Ready(None)));
+/// ```
+#[derive(Debug)]
+pub struct AsyncCommentParser<'a, S> {
+    stream: S,
+    rules: &'a [SyntaxRule<'a>],
+    /// The decoded text, from the line of the last complete comment.
+    buffer: String,
+    /// The byte index in `buffer` to continue parsing from.
+    index: usize,
+    /// The byte index in the whole stream of the start of `buffer`.
+    base: usize,
+    /// The bytes of an incomplete UTF-8 sequence ending the last chunk.
+    partial: Vec<u8>,
+    events: VecDeque<OwnedEvent>,
+    done: bool,
+}
+
+impl<'a, S: ChunkStream> AsyncCommentParser<'a, S> {
+    /// Creates an `AsyncCommentParser` which parses the chunks of
+    /// `stream` based on `rules`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when polled, if any [`SyntaxRule`] contains an empty `&[u8]`.
+    ///
+    /// [`SyntaxRule`]: enum.SyntaxRule.html
+    #[inline]
+    pub fn new(stream: S, rules: &'a [SyntaxRule<'a>]) -> Self {
+        Self {
+            stream,
+            rules,
+            buffer: String::new(),
+            index: 0,
+            base: 0,
+            partial: Vec::new(),
+            events: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Attempts to produce the next comment, like `Stream::poll_next`,
+    /// returning `Poll::Ready(None)` once the stream has ended and all
+    /// comments have been produced.
+    ///
+    /// An error reading the stream is returned as is, after which
+    /// polling may continue.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<OwnedEvent>>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match self.stream.poll_chunk(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(Some(Ok(chunk))) => {
+                    self.partial.extend_from_slice(&chunk);
+                    let text = decode_complete(&mut self.partial);
+                    self.buffer.push_str(&text);
+                    self.parse_buffer();
+                }
+                Poll::Ready(None) => {
+                    let text = String::from_utf8_lossy(&self.partial).into_owned();
+                    self.buffer.push_str(&text);
+                    self.partial.clear();
+                    self.done = true;
+                    self.parse_buffer();
+                }
+            }
+        }
+    }
+
+    /// Returns a future resolving to the next comment, or `None` once
+    /// the stream has ended, i.e. an async version of `Iterator::next`.
+    ///
+    /// *See also [`poll_next`].*
+    ///
+    /// [`poll_next`]: struct.AsyncCommentParser.html#method.poll_next
+    #[inline]
+    pub fn next_comment(&mut self) -> NextComment<'_, 'a, S> {
+        NextComment(self)
+    }
+
+    /// Queues the complete comments in the buffer, and discards the
+    /// text before the line of the last complete comment. Once the
+    /// stream has ended, all remaining comments are complete.
+    fn parse_buffer(&mut self) {
+        {
+            let buffer = &self.buffer;
+            let mut parser =
+                CommentParser::new_from(buffer, self.rules, self.index).include_strings(true);
+            let mut incomplete = None;
+
+            while let Some((start, event)) = parser.next_with_start() {
+                let end = range_in(buffer, event.raw()).end;
+
+                let complete = self.done
+                    || match event.kind() {
+                        EventKind::LineComment | EventKind::Shebang => end < buffer.len(),
+                        EventKind::BlockComment | EventKind::String => {
+                            !event.close_delim().is_empty()
+                        }
+                    };
+                if !complete {
+                    incomplete = Some(start);
+                    break;
+                }
+
+                if !event.is_string() {
+                    self.events.push_back(to_owned(&event, buffer, self.base));
+                }
+                self.index = self.index.max(end);
+            }
+
+            // A delimiter may be split across chunks, so the last line
+            // is parsed again, once more text is read
+            self.index = match incomplete {
+                Some(start) => start,
+                None => find_line_start(buffer, buffer.len()).max(self.index),
+            };
+        }
+
+        let line_start = find_line_start(&self.buffer, self.index);
+        self.buffer.drain(..line_start);
+        self.base += line_start;
+        self.index -= line_start;
+    }
+}
+
+/// Future returned by [`AsyncCommentParser::next_comment`].
+///
+/// [`AsyncCommentParser::next_comment`]: struct.AsyncCommentParser.html#method.next_comment
+#[derive(Debug)]
+pub struct NextComment<'p, 'a, S>(&'p mut AsyncCommentParser<'a, S>);
+
+impl<S: ChunkStream> Future for NextComment<'_, '_, S> {
+    type Output = Option<io::Result<OwnedEvent>>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_next(cx)
+    }
+}
+
+/// Returns `event` as an `OwnedEvent`, where the ranges are byte ranges
+/// in `buffer`, offset by `base`.
+fn to_owned(event: &Event, buffer: &str, base: usize) -> OwnedEvent {
+    let (raw_range, text_range) = event.spans(buffer);
+    OwnedEvent {
+        kind: event.kind(),
+        raw: event.raw().to_string(),
+        text: event.text().to_string(),
+        raw_range: base + raw_range.start..base + raw_range.end,
+        text_range: base + text_range.start..base + text_range.end,
+    }
+}
+
+/// Decodes and removes the bytes of `bytes`, except for an incomplete
+/// UTF-8 sequence at the end, which is left for the next chunk.
+fn decode_complete(bytes: &mut Vec<u8>) -> String {
+    let mut complete = bytes.len();
+
+    for i in (bytes.len().saturating_sub(3)..bytes.len()).rev() {
+        let len = match bytes[i] {
+            0x80..=0xBF => continue,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+        if i + len > bytes.len() {
+            complete = i;
+        }
+        break;
+    }
+
+    let text = String::from_utf8_lossy(&bytes[..complete]).into_owned();
+    bytes.drain(..complete);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::io;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    use crate::{get_syntax, AsyncCommentParser, ChunkStream, EventKind};
+
+    /// A stream which is pending before each chunk.
+    struct Chunks {
+        chunks: VecDeque<io::Result<&'static [u8]>>,
+        ready: bool,
+    }
+
+    impl ChunkStream for Chunks {
+        fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<Vec<u8>>>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+
+            Poll::Ready(
+                self.chunks
+                    .pop_front()
+                    .map(|chunk| chunk.map(<[u8]>::to_vec)),
+            )
+        }
+    }
+
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Waker::from(Arc::new(Noop));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn split_chunks() {
+        let chunks: Vec<io::Result<&'static [u8]>> = vec![
+            Ok(b"int x; /* Fo"),
+            Ok(b"o\nBar */ // Baz \xC3"),
+            Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted")),
+            Ok(b"\xA9\n\"/* Not a comment"),
+            Ok(b" */\"; /"),
+            Ok(b"/ Qux"),
+        ];
+        let stream = Chunks {
+            chunks: chunks.into(),
+            ready: false,
+        };
+        let mut parser = AsyncCommentParser::new(stream, get_syntax("c").unwrap());
+
+        let mut events = Vec::new();
+        let mut errors = 0;
+        while let Some(event) = block_on(parser.next_comment()) {
+            match event {
+                Ok(event) => events.push((event.kind, event.text, event.raw_range)),
+                Err(_) => errors += 1,
+            }
+        }
+
+        assert_eq!(errors, 1);
+        assert_eq!(
+            events,
+            [
+                (EventKind::BlockComment, " Foo\nBar ".to_string(), 7..20),
+                (EventKind::LineComment, " Baz é".to_string(), 14..30),
+                (EventKind::LineComment, " Qux".to_string(), 31..60),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::mem;
 use std::str::from_utf8;
 
 /// The [parser][`CommentParser`] uses a few syntax rules, to be
@@ -15,6 +16,11 @@ use std::str::from_utf8;
 /// Note that [`CommentParser`] panics immediately upon calling [`new`][CommentParser::new],
 /// if any `SyntaxRule` contains an empty `&[u8]`.
 ///
+/// Use the checked constructors, such as [`line_comment`], to catch
+/// empty delimiters when the rule is constructed instead.
+///
+/// [`line_comment`]: enum.SyntaxRule.html#method.line_comment
+///
 /// # Example
 ///
 /// If you want to create syntax rules, for a parser only capturing
@@ -50,6 +56,9 @@ use std::str::from_utf8;
 ///
 /// [CommentParser::new]: struct.CommentParser.html#method.new
 ///
+/// New kinds of rules may be added in the future, so matches on
+/// `SyntaxRule` must include a wildcard arm.
+///
 /// # Unsupported Language
 ///
 /// If you implement syntax rules for an unsupported language, then feel free to submit
@@ -60,13 +69,622 @@ use std::str::from_utf8;
 /// [pull request]: https://github.com/vallentin/comment-parser/pulls
 /// [languages.rs]: https://github.com/vallentin/comment-parser/blob/master/src/languages.rs
 #[derive(Clone)]
+#[non_exhaustive]
 pub enum SyntaxRule<'a> {
     /// `LineComment(start)`
     LineComment(&'a [u8]),
     /// `BlockComment(start, end)`
     BlockComment(&'a [u8], &'a [u8]),
+    /// `NestedBlockComment(start, end)`
+    ///
+    /// A block comment where nested pairs of `start` and `end` are
+    /// part of the comment, such as Racket's `#| a #| b |# c |#`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::NestedBlockComment(b"#|", b"|#");
+    /// ```
+    NestedBlockComment(&'a [u8], &'a [u8]),
+    /// `LineCommentUntil(start, end)`
+    ///
+    /// A line comment which ends at `end` or at the end of the line,
+    /// whichever comes first. For instance in ASN.1, `-- a -- b` is
+    /// the comment ` a ` followed by the code `b`.
+    ///
+    /// When ended by `end`, the comment is produced as an
+    /// [`Event::BlockComment`], otherwise as an [`Event::LineComment`].
+    ///
+    /// [`Event::BlockComment`]: enum.Event.html#variant.BlockComment
+    /// [`Event::LineComment`]: enum.Event.html#variant.LineComment
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::LineCommentUntil(b"--", b"--");
+    /// ```
+    LineCommentUntil(&'a [u8], &'a [u8]),
     /// `String(delimiter)`
     String(&'a [u8]),
+    /// `Char(delimiter)`
+    ///
+    /// A character literal, such as Rust's `'x'` and `'\n'`, which
+    /// is produced as a string. If `delimiter` is not followed by a
+    /// single, possibly escaped, character and another `delimiter`,
+    /// then it is not a character literal and is skipped. For
+    /// instance, Rust's lifetime `'a` in `&'a str`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::Char(b"'");
+    /// ```
+    Char(&'a [u8]),
+    /// `NestedString(start, end)`
+    ///
+    /// A string delimited by `start` and `end`, where nested pairs
+    /// of `start` and `end` are part of the string, and `\` escapes
+    /// the following byte. For instance PostScript's `(a (b) c)`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::NestedString(b"(", b")");
+    /// ```
+    NestedString(&'a [u8], &'a [u8]),
+    /// `DelimitedString(start, end)`
+    ///
+    /// A string delimited by `start` and `end`, where `\` escapes the
+    /// following byte. For instance bash's ANSI-C quoting `$'it\'s'`,
+    /// unlike its `'...'` which has no escapes.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::DelimitedString(b"$'", b"'");
+    /// ```
+    DelimitedString(&'a [u8], &'a [u8]),
+    /// `HereDoc(start)`
+    ///
+    /// A here-document, such as Racket's `#<<END`, where the tag is the
+    /// rest of the line after `start`, excluding surrounding whitespace.
+    /// The string is the following lines, up to a line consisting only
+    /// of the tag. The tag must not be empty.
+    ///
+    /// *See also [`ShellHereDoc`].*
+    ///
+    /// [`ShellHereDoc`]: enum.SyntaxRule.html#variant.ShellHereDoc
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::HereDoc(b"#<<");
+    /// ```
+    HereDoc(&'a [u8]),
+    /// `ShellHereDoc(start)`
+    ///
+    /// A shell here-document, such as `<<EOF` and `<<-'EOF'`, where the
+    /// tag is the optionally quoted word following `start`, and a `-`
+    /// directly after `start` is ignored. The string is the following
+    /// lines, up to a line consisting only of the tag, excluding
+    /// surrounding whitespace. The rest of the line after the tag is
+    /// skipped along with the here-document.
+    ///
+    /// The here-document only starts in command position, i.e. when
+    /// `start` is at the start of the line or preceded by whitespace
+    /// or a word, and is not within an arithmetic `((...))` on the same
+    /// line, such as the left shift in `(( x << 2 ))`. Nor may `start`
+    /// be directly preceded or followed by its last byte, e.g. the
+    /// here-string `<<<` for `<<`. The tag must not be empty.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::ShellHereDoc(b"<<");
+    /// ```
+    ShellHereDoc(&'a [u8]),
+    /// A regular expression literal, such as JavaScript's `/a\/b/g`,
+    /// which is produced as a string. A `/` only starts a regular
+    /// expression, when the preceding code is not an operand, i.e.
+    /// the previous non-whitespace byte is an operator or one of
+    /// `(,:;[{`, or a keyword such as `return` precedes it. Otherwise,
+    /// such as in `a / b / c`, the `/` is a division. This includes a
+    /// `/` after `++`, `--`, `)`, an identifier or `<`, e.g. `a++ / b`
+    /// and the JSX closing tag `</div>`.
+    ///
+    /// The regular expression ends at the first `/` that is neither
+    /// escaped nor within a character class, such as `[/]`, on the
+    /// same line, followed by any flags. A `/` followed by `/` or `*`
+    /// never starts a regular expression.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::RegexLiteral;
+    /// ```
+    RegexLiteral,
+    /// `RawString(prefix)`
+    ///
+    /// A string without escapes, such as Rust's `r"..."` and `r#"..."#`.
+    /// The `prefix` is followed by any number of `#` and then `"`, and
+    /// the string ends at a `"` followed by the same number of `#`.
+    ///
+    /// The `prefix` followed by `#` but not `"`, such as the raw
+    /// identifier `r#type`, is not a string.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::RawString(b"r");
+    /// ```
+    RawString(&'a [u8]),
+    /// A `#!` interpreter directive on the first line.
+    ///
+    /// `Shebang` is only recognized when enabled with
+    /// [`CommentParser::shebang`], otherwise the rule is ignored
+    /// and the line is left for the remaining rules, e.g. a `#`
+    /// line comment.
+    ///
+    /// [`CommentParser::shebang`]: struct.CommentParser.html#method.shebang
+    Shebang,
+    /// `AtColumn(column, rule)`
+    ///
+    /// The `rule` only matches when it starts at the 0-based `column`,
+    /// counted in characters from the start of the line. This is
+    /// useful for fixed-format languages, such as COBOL where a `*`
+    /// in column 7 is a comment.
+    ///
+    /// Columns are counted in bytes instead, if set by
+    /// [`CommentParser::column_mode`].
+    ///
+    /// [`CommentParser::column_mode`]: struct.CommentParser.html#method.column_mode
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::AtColumn(6, &SyntaxRule::LineComment(b"*"));
+    /// ```
+    AtColumn(usize, &'a SyntaxRule<'a>),
+    /// `PrecededBy(bytes, rule)`
+    ///
+    /// The `rule` only matches at the start of a line, or when the
+    /// preceding byte is one of `bytes`. This is useful for languages
+    /// where a comment must start a word, such as shell where
+    /// `${x#foo}` and `$#` are not comments.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::PrecededBy(b" \t;(", &SyntaxRule::LineComment(b"#"));
+    /// ```
+    PrecededBy(&'a [u8], &'a SyntaxRule<'a>),
+    /// `WholeLine(rule)`
+    ///
+    /// The `rule` only matches when only whitespace precedes it on
+    /// the line. For a line comment, this means the comment must be
+    /// the whole line, e.g. `// foo` alone on a line is matched, while
+    /// `code(); // foo` is not.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::WholeLine(&SyntaxRule::LineComment(b"//"));
+    /// ```
+    WholeLine(&'a SyntaxRule<'a>),
+    /// `Unescaped(rule)`
+    ///
+    /// The `rule` only matches when it is not escaped, i.e. preceded
+    /// by an odd number of `\`. This is useful for languages where
+    /// a comment delimiter can be escaped, such as INI dialects where
+    /// `\;` is a literal `;`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::Unescaped(&SyntaxRule::LineComment(b";"));
+    /// ```
+    Unescaped(&'a SyntaxRule<'a>),
+    /// `FirstLineOnly(rule)`
+    ///
+    /// The `rule` only matches when it starts on the first line of the
+    /// text. This is useful for directives only honored on the first
+    /// line, such as an XML declaration `<?xml ... ?>`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::FirstLineOnly(&SyntaxRule::BlockComment(b"<?xml", b"?>"));
+    /// ```
+    FirstLineOnly(&'a SyntaxRule<'a>),
+    /// `CommentsInside(rule)`
+    ///
+    /// The string `rule` is matched as usual, but comments are also
+    /// found inside of the string, e.g. for string-like constructs
+    /// which may contain comments. Comments inside end with the string,
+    /// and are produced directly after it, such that they overlap the
+    /// string when strings are included.
+    /// By default, no comments are found inside of strings.
+    ///
+    /// *See also [`allow_comments_inside`].*
+    ///
+    /// [`allow_comments_inside`]: enum.SyntaxRule.html#method.allow_comments_inside
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::CommentsInside(&SyntaxRule::String(b"`"));
+    /// ```
+    CommentsInside(&'a SyntaxRule<'a>),
+    /// `ValidEscapes(bytes, rule)`
+    ///
+    /// The string `rule` is matched as usual, where `bytes` are the
+    /// bytes which may follow a `\` escape, e.g. `b"nrt0\\\"'"` for
+    /// C-like strings. When enabled by [`strict_escapes`], any other
+    /// escape, such as `\q`, is recorded as a [`Diagnostic::InvalidEscape`].
+    /// Otherwise, `bytes` are ignored.
+    ///
+    /// [`strict_escapes`]: struct.CommentParser.html#method.strict_escapes
+    /// [`Diagnostic::InvalidEscape`]: enum.Diagnostic.html#variant.InvalidEscape
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::ValidEscapes(b"nrt0\\\"", &SyntaxRule::String(b"\""));
+    /// ```
+    ValidEscapes(&'a [u8], &'a SyntaxRule<'a>),
+    /// `Verbatim(rule)`
+    ///
+    /// The string `rule` is matched as usual, except that escapes do
+    /// not apply within it, such as fenced code blocks delimited by
+    /// `` ``` ``, where `\` is a literal backslash.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::Verbatim(&SyntaxRule::String(b"```"));
+    /// ```
+    Verbatim(&'a SyntaxRule<'a>),
+    /// `TagBlockComment(start_name, end_name)`
+    ///
+    /// A block comment delimited by template tags, such as Django's
+    /// `{% comment %}` and `{% endcomment %}`. The tags allow
+    /// whitespace around the name, as well as whitespace control
+    /// with `-`, e.g. `{%- comment -%}`.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// let rule = SyntaxRule::TagBlockComment(b"comment", b"endcomment");
+    /// ```
+    TagBlockComment(&'a [u8], &'a [u8]),
+    /// `TokenBlockComment(start_tokens, end_tokens)`
+    ///
+    /// A block comment delimited by sequences of tokens, separated by
+    /// any amount of spaces or tabs, such as C's `#if 0` and `#endif`.
+    /// The last token must not be followed by an identifier character,
+    /// e.g. `#if 01` is not matched.
+    ///
+    /// The `end_tokens` are matched at the start of a line, after any
+    /// whitespace. Nested preprocessor conditionals, i.e. `#if`, `#ifdef`
+    /// and `#ifndef`, are paired with their own `#endif`, such that
+    /// `#if 0` blocks nest.
+    ///
+    /// ```
+    /// # use comment_parser::SyntaxRule;
+    /// const RULE: SyntaxRule = SyntaxRule::TokenBlockComment(&[b"#if", b"0"], &[b"#endif"]);
+    /// ```
+    TokenBlockComment(&'a [&'a [u8]], &'a [&'a [u8]]),
+}
+
+impl<'a> SyntaxRule<'a> {
+    /// Creates a [`LineComment`] rule, or returns [`SyntaxError::EmptyDelimiter`]
+    /// if `start` is empty.
+    ///
+    /// [`LineComment`]: enum.SyntaxRule.html#variant.LineComment
+    /// [`SyntaxError::EmptyDelimiter`]: enum.SyntaxError.html#variant.EmptyDelimiter
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{SyntaxError, SyntaxRule};
+    /// assert!(SyntaxRule::line_comment(b"//").is_ok());
+    /// assert_eq!(SyntaxRule::line_comment(b"").unwrap_err(), SyntaxError::EmptyDelimiter);
+    /// ```
+    #[inline]
+    pub const fn line_comment(start: &'a [u8]) -> Result<Self, SyntaxError> {
+        if start.is_empty() {
+            Err(SyntaxError::EmptyDelimiter)
+        } else {
+            Ok(SyntaxRule::LineComment(start))
+        }
+    }
+
+    /// Creates a [`BlockComment`] rule, or returns [`SyntaxError::EmptyDelimiter`]
+    /// if `start` or `end` is empty.
+    ///
+    /// [`BlockComment`]: enum.SyntaxRule.html#variant.BlockComment
+    /// [`SyntaxError::EmptyDelimiter`]: enum.SyntaxError.html#variant.EmptyDelimiter
+    #[inline]
+    pub const fn block_comment(start: &'a [u8], end: &'a [u8]) -> Result<Self, SyntaxError> {
+        if start.is_empty() || end.is_empty() {
+            Err(SyntaxError::EmptyDelimiter)
+        } else {
+            Ok(SyntaxRule::BlockComment(start, end))
+        }
+    }
+
+    /// Creates a [`String`] rule, or returns [`SyntaxError::EmptyDelimiter`]
+    /// if `delimiter` is empty.
+    ///
+    /// [`String`]: enum.SyntaxRule.html#variant.String
+    /// [`SyntaxError::EmptyDelimiter`]: enum.SyntaxError.html#variant.EmptyDelimiter
+    #[inline]
+    pub const fn string(delimiter: &'a [u8]) -> Result<Self, SyntaxError> {
+        if delimiter.is_empty() {
+            Err(SyntaxError::EmptyDelimiter)
+        } else {
+            Ok(SyntaxRule::String(delimiter))
+        }
+    }
+
+    /// Returns the rule wrapped in [`CommentsInside`] if `allow` is
+    /// `true`, i.e. comments are also found inside of strings matched
+    /// by the rule. Otherwise, returns the rule unchanged.
+    ///
+    /// [`CommentsInside`]: enum.SyntaxRule.html#variant.CommentsInside
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{CommentParser, SyntaxRule};
+    /// let string = SyntaxRule::String(b"`");
+    /// let rules = [SyntaxRule::LineComment(b"#"), string.allow_comments_inside(true)];
+    ///
+    /// let mut parser = CommentParser::new("`foo # Foo\n`", &rules);
+    /// assert_eq!(parser.next().unwrap().text(), " Foo");
+    /// ```
+    #[inline]
+    pub fn allow_comments_inside(&'a self, allow: bool) -> Self {
+        if allow {
+            SyntaxRule::CommentsInside(self)
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<'a> SyntaxRule<'a> {
+    /// Returns the conflicts between `rules` of different kinds,
+    /// where the starts are identical or one start is a prefix
+    /// of another.
+    ///
+    /// The parser always picks the first matching rule, so rules
+    /// in conflict are ambiguous and likely a mistake. Rules of the
+    /// same kind are not in conflict, e.g. `LineComment(b"///")`
+    /// and `LineComment(b"//")`. [`Shebang`] rules are never in
+    /// conflict, as they only match at the start of the text. Likewise,
+    /// [`RegexLiteral`] rules are not in conflict with rules starting
+    /// with `//` or `/*`, as a regular expression never starts there.
+    ///
+    /// A positionally constrained rule, e.g. [`WholeLine`], preceding
+    /// a rule whose start is a prefix of its own is deliberate, and so
+    /// not in conflict, e.g. MATLAB's `%{` on its own line, and
+    /// otherwise `%`.
+    ///
+    /// [`Shebang`]: enum.SyntaxRule.html#variant.Shebang
+    /// [`RegexLiteral`]: enum.SyntaxRule.html#variant.RegexLiteral
+    /// [`WholeLine`]: enum.SyntaxRule.html#variant.WholeLine
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{get_syntax, Conflict, SyntaxRule};
+    /// let rules = [
+    ///     SyntaxRule::BlockComment(b"/*", b"*/"),
+    ///     SyntaxRule::String(b"/"),
+    /// ];
+    ///
+    /// assert_eq!(SyntaxRule::check_conflicts(&rules), [Conflict::Prefix(1, 0)]);
+    ///
+    /// assert!(SyntaxRule::check_conflicts(get_syntax("rust").unwrap()).is_empty());
+    /// ```
+    pub fn check_conflicts(rules: &[Self]) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+
+        for (i, a) in rules.iter().enumerate() {
+            for (j, b) in rules.iter().enumerate().skip(i + 1) {
+                let positional = a.is_positional();
+                let (a, b) = (a.inner(), b.inner());
+
+                if let (SyntaxRule::Shebang, _) | (_, SyntaxRule::Shebang) = (a, b) {
+                    continue;
+                }
+
+                if let (SyntaxRule::RegexLiteral, rule) | (rule, SyntaxRule::RegexLiteral) = (a, b)
+                {
+                    if rule.start().starts_with(b"//") || rule.start().starts_with(b"/*") {
+                        continue;
+                    }
+                }
+
+                if mem::discriminant(a) == mem::discriminant(b) {
+                    continue;
+                }
+
+                let (a_start, b_start) = (a.start(), b.start());
+
+                if a_start == b_start {
+                    conflicts.push(Conflict::Identical(i, j));
+                } else if b_start.starts_with(a_start) {
+                    conflicts.push(Conflict::Prefix(i, j));
+                } else if a_start.starts_with(b_start) && !positional {
+                    conflicts.push(Conflict::Prefix(j, i));
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Returns the length of the longest start delimiter of `rules`,
+    /// or `0` if `rules` is empty.
+    ///
+    /// The start of a rule is the fixed prefix a match begins with,
+    /// e.g. `#!` for [`Shebang`] and `{%` for [`TagBlockComment`].
+    ///
+    /// [`Shebang`]: enum.SyntaxRule.html#variant.Shebang
+    /// [`TagBlockComment`]: enum.SyntaxRule.html#variant.TagBlockComment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{get_syntax, SyntaxRule};
+    /// let rules = get_syntax("c").unwrap();
+    /// assert_eq!(SyntaxRule::max_rule_len(rules), 2);
+    /// ```
+    #[inline]
+    pub fn max_rule_len(rules: &[Self]) -> usize {
+        rules
+            .iter()
+            .map(Self::start)
+            .map(<[u8]>::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the sorted set of distinct first bytes of the
+    /// start delimiters of `rules`. A match can only begin
+    /// at one of these bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use comment_parser::{get_syntax, SyntaxRule};
+    /// let rules = get_syntax("c").unwrap();
+    /// assert_eq!(SyntaxRule::first_bytes(rules), b"\"/");
+    /// ```
+    pub fn first_bytes(rules: &[Self]) -> Vec<u8> {
+        let mut bytes = rules
+            .iter()
+            .filter_map(|rule| rule.start().first().copied())
+            .collect::<Vec<_>>();
+        bytes.sort_unstable();
+        bytes.dedup();
+        bytes
+    }
+}
+
+/// `Conflict` is a conflict between two rules, returned by
+/// [`SyntaxRule::check_conflicts`]. The rules are referred
+/// to by their index.
+///
+/// [`SyntaxRule::check_conflicts`]: enum.SyntaxRule.html#method.check_conflicts
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Conflict {
+    /// `Identical(a, b)`, the rules have identical starts.
+    Identical(usize, usize),
+    /// `Prefix(a, b)`, the start of rule `a` is a prefix of
+    /// the start of rule `b`.
+    Prefix(usize, usize),
+}
+
+/// `Capabilities` reports which [`SyntaxRule`] variants are supported
+/// by this version of the crate, returned by [`capabilities`].
+///
+/// Each field is `true` if the variant of the same name is supported.
+/// Fields are added along with new variants, so `Capabilities` cannot
+/// be constructed or exhaustively destructured outside of the crate.
+///
+/// [`SyntaxRule`]: enum.SyntaxRule.html
+/// [`capabilities`]: fn.capabilities.html
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Capabilities {
+    /// See [`SyntaxRule::LineComment`](enum.SyntaxRule.html#variant.LineComment).
+    pub line_comment: bool,
+    /// See [`SyntaxRule::BlockComment`](enum.SyntaxRule.html#variant.BlockComment).
+    pub block_comment: bool,
+    /// See [`SyntaxRule::NestedBlockComment`](enum.SyntaxRule.html#variant.NestedBlockComment).
+    pub nested_block_comment: bool,
+    /// See [`SyntaxRule::LineCommentUntil`](enum.SyntaxRule.html#variant.LineCommentUntil).
+    pub line_comment_until: bool,
+    /// See [`SyntaxRule::String`](enum.SyntaxRule.html#variant.String).
+    pub string: bool,
+    /// See [`SyntaxRule::Char`](enum.SyntaxRule.html#variant.Char).
+    pub char: bool,
+    /// See [`SyntaxRule::NestedString`](enum.SyntaxRule.html#variant.NestedString).
+    pub nested_string: bool,
+    /// See [`SyntaxRule::DelimitedString`](enum.SyntaxRule.html#variant.DelimitedString).
+    pub delimited_string: bool,
+    /// See [`SyntaxRule::RawString`](enum.SyntaxRule.html#variant.RawString).
+    pub raw_string: bool,
+    /// See [`SyntaxRule::HereDoc`](enum.SyntaxRule.html#variant.HereDoc).
+    pub here_doc: bool,
+    /// See [`SyntaxRule::ShellHereDoc`](enum.SyntaxRule.html#variant.ShellHereDoc).
+    pub shell_here_doc: bool,
+    /// See [`SyntaxRule::RegexLiteral`](enum.SyntaxRule.html#variant.RegexLiteral).
+    pub regex_literal: bool,
+    /// See [`SyntaxRule::Shebang`](enum.SyntaxRule.html#variant.Shebang).
+    pub shebang: bool,
+    /// See [`SyntaxRule::AtColumn`](enum.SyntaxRule.html#variant.AtColumn).
+    pub at_column: bool,
+    /// See [`SyntaxRule::PrecededBy`](enum.SyntaxRule.html#variant.PrecededBy).
+    pub preceded_by: bool,
+    /// See [`SyntaxRule::WholeLine`](enum.SyntaxRule.html#variant.WholeLine).
+    pub whole_line: bool,
+    /// See [`SyntaxRule::Unescaped`](enum.SyntaxRule.html#variant.Unescaped).
+    pub unescaped: bool,
+    /// See [`SyntaxRule::FirstLineOnly`](enum.SyntaxRule.html#variant.FirstLineOnly).
+    pub first_line_only: bool,
+    /// See [`SyntaxRule::CommentsInside`](enum.SyntaxRule.html#variant.CommentsInside).
+    pub comments_inside: bool,
+    /// See [`SyntaxRule::ValidEscapes`](enum.SyntaxRule.html#variant.ValidEscapes).
+    pub valid_escapes: bool,
+    /// See [`SyntaxRule::Verbatim`](enum.SyntaxRule.html#variant.Verbatim).
+    pub verbatim: bool,
+    /// See [`SyntaxRule::TagBlockComment`](enum.SyntaxRule.html#variant.TagBlockComment).
+    pub tag_block_comment: bool,
+    /// See [`SyntaxRule::TokenBlockComment`](enum.SyntaxRule.html#variant.TokenBlockComment).
+    pub token_block_comment: bool,
+}
+
+/// Returns the [`Capabilities`] of this version of the crate, i.e.
+/// which [`SyntaxRule`] variants are supported.
+///
+/// This is useful for downstream code, to check for features at
+/// runtime, e.g. whether nested strings are supported.
+///
+/// [`Capabilities`]: struct.Capabilities.html
+/// [`SyntaxRule`]: enum.SyntaxRule.html
+///
+/// # Example
+///
+/// ```
+/// use comment_parser::capabilities;
+///
+/// assert!(capabilities().nested_string);
+/// ```
+pub const fn capabilities() -> Capabilities {
+    Capabilities {
+        line_comment: true,
+        block_comment: true,
+        nested_block_comment: true,
+        line_comment_until: true,
+        string: true,
+        char: true,
+        nested_string: true,
+        delimited_string: true,
+        raw_string: true,
+        here_doc: true,
+        shell_here_doc: true,
+        regex_literal: true,
+        shebang: true,
+        at_column: true,
+        preceded_by: true,
+        whole_line: true,
+        unescaped: true,
+        first_line_only: true,
+        comments_inside: true,
+        valid_escapes: true,
+        verbatim: true,
+        tag_block_comment: true,
+        token_block_comment: true,
+    }
+}
+
+/// `SyntaxError` is an error that can be returned by the checked
+/// [`SyntaxRule`] constructors, such as [`SyntaxRule::line_comment`].
+///
+/// [`SyntaxRule`]: enum.SyntaxRule.html
+/// [`SyntaxRule::line_comment`]: enum.SyntaxRule.html#method.line_comment
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SyntaxError {
+    /// A delimiter is empty, which would cause [`CommentParser::new`] to panic.
+    ///
+    /// [`CommentParser::new`]: struct.CommentParser.html#method.new
+    EmptyDelimiter,
 }
 
 impl<'a> fmt::Debug for SyntaxRule<'a> {
@@ -100,6 +718,208 @@ impl<'a> fmt::Debug for SyntaxRule<'a> {
                     fmt.debug_tuple("String").field(start).finish()
                 }
             }
+            LineCommentUntil(start, end) => fmt
+                .debug_tuple("LineCommentUntil")
+                .field(&Delim(start))
+                .field(&Delim(end))
+                .finish(),
+            NestedBlockComment(start, end) => fmt
+                .debug_tuple("NestedBlockComment")
+                .field(&Delim(start))
+                .field(&Delim(end))
+                .finish(),
+            Char(delimiter) => fmt.debug_tuple("Char").field(&Delim(delimiter)).finish(),
+            NestedString(start, end) => fmt
+                .debug_tuple("NestedString")
+                .field(&Delim(start))
+                .field(&Delim(end))
+                .finish(),
+            DelimitedString(start, end) => fmt
+                .debug_tuple("DelimitedString")
+                .field(&Delim(start))
+                .field(&Delim(end))
+                .finish(),
+            RawString(prefix) => fmt.debug_tuple("RawString").field(&Delim(prefix)).finish(),
+            HereDoc(start) => fmt.debug_tuple("HereDoc").field(&Delim(start)).finish(),
+            ShellHereDoc(start) => fmt
+                .debug_tuple("ShellHereDoc")
+                .field(&Delim(start))
+                .finish(),
+            Shebang => fmt.write_str("Shebang"),
+            RegexLiteral => fmt.write_str("RegexLiteral"),
+            AtColumn(column, rule) => fmt
+                .debug_tuple("AtColumn")
+                .field(column)
+                .field(rule)
+                .finish(),
+            WholeLine(rule) => fmt.debug_tuple("WholeLine").field(rule).finish(),
+            Unescaped(rule) => fmt.debug_tuple("Unescaped").field(rule).finish(),
+            FirstLineOnly(rule) => fmt.debug_tuple("FirstLineOnly").field(rule).finish(),
+            CommentsInside(rule) => fmt.debug_tuple("CommentsInside").field(rule).finish(),
+            Verbatim(rule) => fmt.debug_tuple("Verbatim").field(rule).finish(),
+            PrecededBy(bytes, rule) => fmt
+                .debug_tuple("PrecededBy")
+                .field(&Delim(bytes))
+                .field(rule)
+                .finish(),
+            ValidEscapes(bytes, rule) => fmt
+                .debug_tuple("ValidEscapes")
+                .field(&Delim(bytes))
+                .field(rule)
+                .finish(),
+            TagBlockComment(start, end) => fmt
+                .debug_tuple("TagBlockComment")
+                .field(&Delim(start))
+                .field(&Delim(end))
+                .finish(),
+            TokenBlockComment(start, end) => fmt
+                .debug_tuple("TokenBlockComment")
+                .field(&start.iter().map(|t| Delim(t)).collect::<Vec<_>>())
+                .field(&end.iter().map(|t| Delim(t)).collect::<Vec<_>>())
+                .finish(),
+        }
+    }
+}
+
+/// Formats a delimiter as a `str` if it is valid UTF-8,
+/// otherwise as bytes.
+struct Delim<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for Delim<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Ok(delim) = from_utf8(self.0) {
+            delim.fmt(fmt)
+        } else {
+            self.0.fmt(fmt)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Conflict, SyntaxError, SyntaxRule};
+    use crate::get_syntax;
+
+    #[test]
+    fn max_rule_len() {
+        let rust = get_syntax("rust").unwrap();
+        let python = get_syntax("python").unwrap();
+
+        assert_eq!(SyntaxRule::max_rule_len(rust), 3);
+        assert_eq!(SyntaxRule::max_rule_len(python), 3);
+        assert_eq!(SyntaxRule::max_rule_len(&[]), 0);
+        assert_eq!(
+            SyntaxRule::max_rule_len(&[SyntaxRule::TokenBlockComment(&[], &[b"#endif"])]),
+            0
+        );
+    }
+
+    #[test]
+    fn first_bytes() {
+        let rust = get_syntax("rust").unwrap();
+        let python = get_syntax("python").unwrap();
+
+        assert_eq!(SyntaxRule::first_bytes(rust), b"\"'/r");
+        assert_eq!(SyntaxRule::first_bytes(python), b"\"#'");
+        assert!(SyntaxRule::first_bytes(&[]).is_empty());
+        assert!(SyntaxRule::first_bytes(&[
+            SyntaxRule::TokenBlockComment(&[], &[b"#endif"]),
+            SyntaxRule::TokenBlockComment(&[b""], &[b"#endif"]),
+        ])
+        .is_empty());
+    }
+
+    #[test]
+    fn debug_token_block_comment() {
+        let rule = SyntaxRule::TokenBlockComment(&[b"#if", b"0"], &[b"#endif"]);
+        assert_eq!(
+            format!("{:?}", rule),
+            "TokenBlockComment([\"#if\", \"0\"], [\"#endif\"])"
+        );
+    }
+
+    #[test]
+    fn debug_tag_block_comment() {
+        let rule = SyntaxRule::TagBlockComment(b"comment", b"endcomment");
+        assert_eq!(
+            format!("{:?}", rule),
+            "TagBlockComment(\"comment\", \"endcomment\")"
+        );
+    }
+
+    #[test]
+    fn checked_constructors_empty() {
+        assert_eq!(
+            SyntaxRule::line_comment(b"").unwrap_err(),
+            SyntaxError::EmptyDelimiter
+        );
+        assert_eq!(
+            SyntaxRule::block_comment(b"", b"*/").unwrap_err(),
+            SyntaxError::EmptyDelimiter
+        );
+        assert_eq!(
+            SyntaxRule::block_comment(b"/*", b"").unwrap_err(),
+            SyntaxError::EmptyDelimiter
+        );
+        assert_eq!(
+            SyntaxRule::string(b"").unwrap_err(),
+            SyntaxError::EmptyDelimiter
+        );
+    }
+
+    #[test]
+    fn checked_constructors_non_empty() {
+        let rules = [
+            SyntaxRule::line_comment(b"//").unwrap(),
+            SyntaxRule::block_comment(b"/*", b"*/").unwrap(),
+            SyntaxRule::string(b"\"").unwrap(),
+        ];
+
+        let mut parser = crate::CommentParser::new("// Foo", &rules);
+        assert!(parser.next().is_some());
+    }
+
+    #[test]
+    fn check_conflicts_overlapping() {
+        use SyntaxRule::*;
+
+        let rules = [
+            LineComment(b"#"),
+            BlockComment(b"/*", b"*/"),
+            String(b"/*"),
+            String(b"#!"),
+        ];
+
+        assert_eq!(
+            SyntaxRule::check_conflicts(&rules),
+            [Conflict::Prefix(0, 3), Conflict::Identical(1, 2)]
+        );
+    }
+
+    #[test]
+    fn check_conflicts_disjoint() {
+        use SyntaxRule::*;
+
+        let rules = [
+            LineComment(b"///"),
+            LineComment(b"//"),
+            BlockComment(b"/*", b"*/"),
+            String(b"\""),
+        ];
+
+        assert!(SyntaxRule::check_conflicts(&rules).is_empty());
+    }
+
+    #[test]
+    fn check_conflicts_positional() {
+        use SyntaxRule::*;
+
+        assert!(SyntaxRule::check_conflicts(get_syntax("matlab").unwrap()).is_empty());
+
+        let rules = [LineComment(b"%"), WholeLine(&BlockComment(b"%{", b"%}"))];
+        assert_eq!(
+            SyntaxRule::check_conflicts(&rules),
+            [Conflict::Prefix(0, 1)]
+        );
+    }
+}
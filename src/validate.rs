@@ -0,0 +1,194 @@
+use std::ops::Range;
+
+use line_span::find_line_start;
+
+use crate::parse::{range_in, CommentParser, Event, ParseError};
+use crate::syntax::SyntaxRule;
+
+/// Common starts of comments, used to guess whether a line looks
+/// like a comment.
+const COMMENT_MARKERS: [&str; 9] = ["//", "/*", "#", "--", ";", "%", "<!--", "(*", "{-"];
+
+/// `Issue` is a suspicious outcome of parsing a sample with a set of
+/// rules, returned by [`validate_rules_against`]. Positions are byte
+/// indices in the sample.
+///
+/// [`validate_rules_against`]: fn.validate_rules_against.html
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Issue {
+    /// `CommentInString(index)`, the comment starting at `index` seems
+    /// to be inside a string, which no rule matched.
+    CommentInString(usize),
+    /// `Unterminated(error)`, a block comment or string is not closed
+    /// before the end of the sample.
+    Unterminated(ParseError),
+    /// `NoComments(index)`, no comments were found, even though the
+    /// line with a comment-like marker at `index` looks like one.
+    NoComments(usize),
+}
+
+/// Parses `sample` based on `rules`, and returns the [`Issue`]s
+/// which suggest that `rules` do not match the language of `sample`.
+///
+/// This is a heuristic aid for adding languages. A sample should
+/// contain the comments and strings of the language, such that
+/// e.g. a missing string rule results in comments found inside
+/// of strings.
+///
+/// [`Issue`]: enum.Issue.html
+///
+/// # Panics
+///
+/// Panics if any [`SyntaxRule`] contains an empty `&[u8]`.
+///
+/// [`SyntaxRule`]: enum.SyntaxRule.html
+///
+/// # Example
+///
+/// ```
+/// # use comment_parser::{get_syntax, validate_rules_against, Issue, SyntaxRule};
+/// let sample = "puts(\"// Foo\"); // Bar\n";
+///
+/// let rules = get_syntax("c").unwrap();
+/// assert_eq!(validate_rules_against(sample, rules), Ok(()));
+///
+/// let rules = [SyntaxRule::LineComment(b"//")];
+/// assert_eq!(
+///     validate_rules_against(sample, &rules),
+///     Err(vec![Issue::CommentInString(6)])
+/// );
+/// ```
+pub fn validate_rules_against(sample: &str, rules: &[SyntaxRule]) -> Result<(), Vec<Issue>> {
+    let mut issues = Vec::new();
+    let mut strings = Vec::new();
+    let mut comments = 0;
+
+    let mut parser = CommentParser::new(sample, rules).include_strings(true);
+
+    while let Some((start, event, unterminated)) = parser.next_checked() {
+        if unterminated {
+            match event {
+                Event::BlockComment(..) => issues.push(Issue::Unterminated(
+                    ParseError::UnterminatedBlockComment(start),
+                )),
+                Event::String(..) => {
+                    issues.push(Issue::Unterminated(ParseError::UnterminatedString(start)))
+                }
+                _ => {}
+            }
+        }
+
+        if event.is_string() {
+            strings.push(range_in(sample, event.raw()));
+            continue;
+        }
+
+        comments += 1;
+
+        if in_unmatched_string(sample, start, event.raw(), &strings) {
+            issues.push(Issue::CommentInString(start));
+        }
+    }
+
+    if comments == 0 {
+        let marker = sample.lines().find_map(|line| {
+            let trimmed = line.trim_start();
+            if COMMENT_MARKERS.iter().any(|m| trimmed.starts_with(m)) {
+                Some(range_in(sample, trimmed).start)
+            } else {
+                None
+            }
+        });
+
+        if let Some(index) = marker {
+            issues.push(Issue::NoComments(index));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+/// Returns `true` if the code before the comment at `start`, on the
+/// same line, has an odd number of quotes outside of `strings`, and
+/// the first line of the comment contains the same quote.
+fn in_unmatched_string(sample: &str, start: usize, raw: &str, strings: &[Range<usize>]) -> bool {
+    let line_start = find_line_start(sample, start);
+    let bytes = sample.as_bytes();
+    let first_line = raw.lines().next().unwrap_or("");
+
+    [b'"', b'\''].iter().any(|&quote| {
+        let count = (line_start..start)
+            .filter(|&i| bytes[i] == quote && (i == 0 || bytes[i - 1] != b'\\'))
+            .filter(|i| !strings.iter().any(|string| string.contains(i)))
+            .count();
+
+        count % 2 == 1 && first_line.as_bytes().contains(&quote)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_syntax, validate_rules_against, Issue, ParseError, SyntaxRule};
+
+    #[test]
+    fn missing_string_rule() {
+        let sample = "# Foo\nprint('# Bar', \"# Baz\")  # Qux\n";
+
+        let rules = get_syntax("python").unwrap();
+        assert_eq!(validate_rules_against(sample, rules), Ok(()));
+
+        let rules = [SyntaxRule::LineComment(b"#")];
+        assert_eq!(
+            validate_rules_against(sample, &rules),
+            Err(vec![Issue::CommentInString(13)])
+        );
+
+        let rules = [SyntaxRule::LineComment(b"#"), SyntaxRule::String(b"'")];
+        assert_eq!(
+            validate_rules_against(sample, &rules),
+            Err(vec![Issue::CommentInString(22)])
+        );
+    }
+
+    #[test]
+    fn unterminated() {
+        let sample = "let s = 'foo'; /* Bar */\n";
+        let rules = [
+            SyntaxRule::BlockComment(b"/*", b"*/"),
+            SyntaxRule::String(b"\""),
+            SyntaxRule::String(b"'"),
+        ];
+        assert_eq!(validate_rules_against(sample, &rules), Ok(()));
+
+        // The end delimiter is mistyped
+        let rules = [
+            SyntaxRule::BlockComment(b"/*", b"*)"),
+            SyntaxRule::String(b"\""),
+            SyntaxRule::String(b"'"),
+        ];
+        assert_eq!(
+            validate_rules_against(sample, &rules),
+            Err(vec![Issue::Unterminated(
+                ParseError::UnterminatedBlockComment(15)
+            )])
+        );
+    }
+
+    #[test]
+    fn no_comments() {
+        let sample = "x = 1\n  -- Foo\n";
+
+        let rules = get_syntax("haskell").unwrap();
+        assert_eq!(validate_rules_against(sample, rules), Ok(()));
+
+        let rules = get_syntax("c").unwrap();
+        assert_eq!(
+            validate_rules_against(sample, rules),
+            Err(vec![Issue::NoComments(8)])
+        );
+    }
+}